@@ -0,0 +1,161 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#[macro_use]
+extern crate criterion;
+use criterion::{BatchSize, Criterion};
+
+extern crate arrow;
+
+use arrow::array::*;
+use arrow::datatypes::{DataType, Field};
+use arrow::ffi::{
+    export_contiguous, import_primitive_columns, ArrowArray, ExportContext,
+    FFI_ArrowArray, FFI_ArrowSchema,
+};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+fn create_data_with_nulls(size: usize) -> ArrayData {
+    let array: Float64Array = (0..size)
+        .map(|i| if i % 2 == 0 { Some(1.0) } else { None })
+        .collect();
+    array.data().clone()
+}
+
+// what a consumer that owns its data does with a regular export: one copy per buffer
+fn copy_buffers(data: &ArrayData) -> (Vec<u8>, Vec<u8>) {
+    let validity = data.null_buffer().unwrap().as_slice().to_vec();
+    let values = data.buffers()[0].as_slice().to_vec();
+    (validity, values)
+}
+
+// what a consumer that owns its data does with a contiguous export: a single copy
+fn copy_contiguous(data: &ArrayData) -> Vec<u8> {
+    let start = data.null_buffer().unwrap().as_ptr();
+    let values = &data.buffers()[0];
+    let len = values.as_ptr() as usize + values.len() - start as usize;
+    unsafe { std::slice::from_raw_parts(start, len) }.to_vec()
+}
+
+fn ffi_benchmark(c: &mut Criterion) {
+    let data = create_data_with_nulls(4096);
+
+    let exported = ArrowArray::try_from(data.clone()).unwrap();
+    let imported = ArrayData::try_from(exported).unwrap();
+    c.bench_function("ffi copy buffers 4096", |b| {
+        b.iter(|| copy_buffers(&imported))
+    });
+
+    let exported = export_contiguous(data).unwrap();
+    let imported = ArrayData::try_from(exported).unwrap();
+    c.bench_function("ffi copy contiguous 4096", |b| {
+        b.iter(|| copy_contiguous(&imported))
+    });
+}
+
+fn create_wide_field(n_fields: usize) -> Field {
+    let fields = (0..n_fields)
+        .map(|i| Field::new(&format!("column_{}", i), DataType::Float64, true))
+        .collect();
+    Field::new("", DataType::Struct(fields), false)
+}
+
+fn ffi_schema_benchmark(c: &mut Criterion) {
+    let field = create_wide_field(100);
+
+    c.bench_function("ffi export schema 100 fields", |b| {
+        b.iter(|| FFI_ArrowSchema::try_new(field.clone()).unwrap())
+    });
+
+    let mut context = ExportContext::new();
+    c.bench_function("ffi export schema 100 fields with context", |b| {
+        b.iter(|| context.export_schema(field.clone()).unwrap())
+    });
+}
+
+fn ffi_shared_schema_benchmark(c: &mut Criterion) {
+    let data = create_data_with_nulls(64);
+    let field = Field::new("a", DataType::Float64, true);
+
+    c.bench_function("ffi export 10000 arrays", |b| {
+        b.iter(|| {
+            (0..10000)
+                .map(|_| unsafe { ArrowArray::try_new_full(data.clone(), field.clone()) })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        })
+    });
+
+    c.bench_function("ffi export 10000 arrays with shared schema", |b| {
+        b.iter(|| {
+            let schema = Arc::new(FFI_ArrowSchema::try_new(field.clone()).unwrap());
+            (0..10000)
+                .map(|_| unsafe {
+                    ArrowArray::try_new_with_schema(data.clone(), schema.clone())
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        })
+    });
+}
+
+// exports `n_columns` Float64 columns, as a producer of a wide batch would
+fn export_columns(
+    n_columns: usize,
+) -> (Vec<*const FFI_ArrowArray>, Vec<*const FFI_ArrowSchema>) {
+    let data = create_data_with_nulls(64);
+    (0..n_columns)
+        .map(|_| ArrowArray::into_raw(ArrowArray::try_from(data.clone()).unwrap()))
+        .unzip()
+}
+
+fn ffi_primitive_columns_benchmark(c: &mut Criterion) {
+    c.bench_function("ffi import 500 columns", |b| {
+        b.iter_batched(
+            || export_columns(500),
+            |(arrays, schemas)| {
+                arrays
+                    .into_iter()
+                    .zip(schemas.into_iter())
+                    .map(|(array, schema)| unsafe { make_array_from_raw(array, schema) })
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    c.bench_function("ffi import 500 primitive columns", |b| {
+        b.iter_batched(
+            || export_columns(500),
+            |(arrays, schemas)| unsafe {
+                import_primitive_columns(&arrays, &schemas, &DataType::Float64).unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    ffi_benchmark,
+    ffi_schema_benchmark,
+    ffi_shared_schema_benchmark,
+    ffi_primitive_columns_benchmark
+);
+criterion_main!(benches);
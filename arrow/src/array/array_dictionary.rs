@@ -110,6 +110,13 @@ impl<'a, K: ArrowPrimitiveType> DictionaryArray<K> {
     pub fn is_ordered(&self) -> bool {
         self.is_ordered
     }
+
+    /// Sets whether the values of this dictionary are ordered, e.g. as declared by the
+    /// producer of an array imported from the C Data Interface.
+    pub(crate) fn with_is_ordered(mut self, is_ordered: bool) -> Self {
+        self.is_ordered = is_ordered;
+        self
+    }
 }
 
 /// Constructs a `DictionaryArray` from an array data reference.
@@ -31,6 +31,7 @@ use crate::array::*;
 use crate::buffer::{Buffer, MutableBuffer};
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
+use crate::ffi;
 use crate::util::bit_util;
 
 ///  Converts a `MutableBuffer` to a `BufferBuilder<T>`.
@@ -703,6 +704,37 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
         Ok(())
     }
 
+    /// Appends the values of a primitive array exported to the C Data Interface, reading its
+    /// buffers directly instead of importing it first. The array is not released.
+    /// # Safety
+    /// Assumes that `array` and `schema` represent a valid C Data Interface.
+    /// # Error
+    /// Errors if the type of `schema` is not `T`, ignoring the timezone of timestamps.
+    pub unsafe fn append_ffi(
+        &mut self,
+        array: &ffi::FFI_ArrowArray,
+        schema: &ffi::FFI_ArrowSchema,
+    ) -> Result<()> {
+        let (validity, values) =
+            ffi::primitive_slices::<T::Native>(array, schema, &T::DATA_TYPE)?;
+        match validity {
+            Some(validity) => {
+                self.materialize_bitmap_builder();
+                let b = self.bitmap_builder.as_mut().unwrap();
+                let offset = array.offset();
+                (offset..offset + values.len())
+                    .for_each(|i| b.append(bit_util::get_bit(validity, i)));
+            }
+            None => {
+                if let Some(b) = self.bitmap_builder.as_mut() {
+                    b.append_n(values.len(), true);
+                }
+            }
+        }
+        self.values_builder.append_slice(values);
+        Ok(())
+    }
+
     /// Builds the `PrimitiveArray` and reset this builder.
     pub fn finish(&mut self) -> PrimitiveArray<T> {
         let len = self.len();
@@ -2153,6 +2185,8 @@ where
 mod tests {
     use super::*;
 
+    use std::convert::TryFrom;
+
     use crate::array::Array;
     use crate::bitmap::Bitmap;
 
@@ -2577,6 +2611,62 @@ mod tests {
         assert_eq!(0, builder.len());
     }
 
+    #[test]
+    fn test_primitive_array_builder_append_ffi() -> Result<()> {
+        let sliced = Int32Array::from(vec![None, Some(5), None, Some(7)]).slice(1, 3);
+        let arrays = vec![
+            Int32Array::from(vec![Some(1), None]).data().clone(),
+            Int32Array::from(vec![3, 4]).data().clone(),
+            sliced.data().clone(),
+        ];
+
+        let mut builder = Int32Builder::new(7);
+        for data in arrays {
+            // export it
+            let (array, schema) =
+                ffi::ArrowArray::into_raw(ffi::ArrowArray::try_from(data)?);
+
+            // (simulate consumer) append it, and release it
+            unsafe { builder.append_ffi(&*array, &*schema) }?;
+            drop(unsafe { ffi::ArrowArray::try_from_raw(array, schema) }?);
+        }
+
+        let expected = Int32Array::from(vec![
+            Some(1),
+            None,
+            Some(3),
+            Some(4),
+            Some(5),
+            None,
+            Some(7),
+        ]);
+        assert_eq!(builder.finish(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_primitive_array_builder_append_ffi_timezone() -> Result<()> {
+        let array =
+            TimestampMicrosecondArray::from_vec(vec![1, 2], Some("UTC".to_string()));
+        let (array, schema) =
+            ffi::ArrowArray::into_raw(ffi::ArrowArray::try_from(array.data().clone())?);
+        assert_eq!(unsafe { &*schema }.format(), "tsu:UTC");
+
+        // the timezone does not change the values
+        let mut builder = TimestampMicrosecondBuilder::new(2);
+        unsafe { builder.append_ffi(&*array, &*schema) }?;
+        // but the unit does
+        let mut other = TimestampMillisecondBuilder::new(2);
+        assert!(unsafe { other.append_ffi(&*array, &*schema) }.is_err());
+        drop(unsafe { ffi::ArrowArray::try_from_raw(array, schema) }?);
+
+        assert_eq!(
+            builder.finish(),
+            TimestampMicrosecondArray::from(vec![1, 2])
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_list_array_builder() {
         let values_builder = Int32Builder::new(10);
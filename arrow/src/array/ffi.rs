@@ -25,8 +25,11 @@ use crate::{
     ffi::ArrowArrayRef,
 };
 
-use super::ArrayData;
+use super::{Array, ArrayData, ArrayRef};
 
+/// Imports an [ArrayData] from the C Data Interface, without creating an array.
+/// Use [make_array](super::make_array) on the result to obtain an [Array], or
+/// [make_array_from_raw](super::make_array_from_raw) to import from raw pointers at once.
 impl TryFrom<ffi::ArrowArray> for ArrayData {
     type Error = ArrowError;
 
@@ -43,14 +46,55 @@ impl TryFrom<ArrayData> for ffi::ArrowArray {
     }
 }
 
+/// Exports an [ArrayData] to the C Data Interface without cloning it at the call site.
+/// Only the `Arc`s backing its buffers and children are cloned.
+/// ```
+/// use std::convert::TryFrom;
+/// use arrow::array::{Array, Int32Array};
+/// use arrow::ffi::ArrowArray;
+/// # fn main() -> arrow::error::Result<()> {
+/// let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+///
+/// let exported = ArrowArray::try_from(array.data())?;
+/// let (array_ptr, schema_ptr) = ArrowArray::into_raw(exported);
+/// // the consumer takes ownership of the pointers and releases them
+/// # drop(unsafe { ArrowArray::try_from_raw(array_ptr, schema_ptr) }?);
+/// # Ok(())
+/// # }
+/// ```
+impl TryFrom<&ArrayData> for ffi::ArrowArray {
+    type Error = ArrowError;
+
+    fn try_from(value: &ArrayData) -> Result<Self> {
+        unsafe { ffi::ArrowArray::try_new(value.clone()) }
+    }
+}
+
+impl TryFrom<ArrayRef> for ffi::ArrowArray {
+    type Error = ArrowError;
+
+    fn try_from(value: ArrayRef) -> Result<Self> {
+        unsafe { ffi::ArrowArray::from_array(value.as_ref()) }
+    }
+}
+
+impl TryFrom<&dyn Array> for ffi::ArrowArray {
+    type Error = ArrowError;
+
+    fn try_from(value: &dyn Array) -> Result<Self> {
+        unsafe { ffi::ArrowArray::from_array(value) }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::error::Result;
     use crate::{
         array::{
-            Array, ArrayData, BooleanArray, Int64Array, StructArray, UInt32Array,
-            UInt64Array,
+            Array, ArrayData, ArrayRef, BooleanArray, Int32Array, Int64Array,
+            StructArray, UInt32Array, UInt64Array,
         },
+        buffer::Buffer,
         datatypes::{DataType, Field},
         ffi::ArrowArray,
     };
@@ -127,4 +171,72 @@ mod tests {
         let data = array.data();
         test_round_trip(data)
     }
+
+    #[test]
+    fn test_struct_duplicate_names() -> Result<()> {
+        let array = StructArray::from(vec![
+            (
+                Field::new("x", DataType::UInt32, false),
+                Arc::new(UInt32Array::from(vec![1, 2])) as Arc<dyn Array>,
+            ),
+            (
+                Field::new("x", DataType::Boolean, false),
+                Arc::new(BooleanArray::from(vec![true, false])) as Arc<dyn Array>,
+            ),
+        ]);
+        let data = array.data();
+        test_round_trip(data)?;
+
+        // both children are kept, by position
+        let array = ArrowArray::try_from(data.clone())?;
+        let array = StructArray::from(ArrayData::try_from(array)?);
+        assert_eq!(array.num_columns(), 2);
+        assert_eq!(array.column_names(), vec!["x", "x"]);
+        assert_eq!(array.column(0).data_type(), &DataType::UInt32);
+        assert_eq!(array.column(1).data_type(), &DataType::Boolean);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_array() -> Result<()> {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]));
+
+        let exported = ArrowArray::try_from(array.as_ref())?;
+        assert_eq!(&ArrayData::try_from(exported)?, array.data());
+
+        let exported = ArrowArray::try_from(array.clone())?;
+        assert_eq!(&ArrayData::try_from(exported)?, array.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_struct() -> Result<()> {
+        let data = ArrayData::builder(DataType::Struct(vec![]))
+            .len(5)
+            .null_bit_buffer(Buffer::from([0b00010110]))
+            .build();
+        test_round_trip(&data)?;
+
+        let array = StructArray::from(ArrayData::try_from(ArrowArray::try_from(data)?)?);
+        assert_eq!(array.len(), 5);
+        assert_eq!(array.null_count(), 2);
+        assert_eq!(array.num_columns(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_data_from_ffi() -> Result<()> {
+        let array = UInt32Array::from(vec![Some(2), None, Some(1)]);
+        let (array_ptr, schema_ptr) =
+            ArrowArray::into_raw(ArrowArray::try_from(array.data().clone())?);
+
+        let imported = unsafe { ArrowArray::try_from_raw(array_ptr, schema_ptr) }?;
+        let data = ArrayData::try_from(imported)?;
+        assert_eq!(data.data_type(), &DataType::UInt32);
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.null_count(), 1);
+        let values = unsafe { data.buffers()[0].typed_data::<u32>() };
+        assert_eq!((values[0], values[2]), (2, 1));
+        Ok(())
+    }
 }
@@ -77,24 +77,102 @@ To export an array, create an `ArrowArray` using [ArrowArray::try_new].
 */
 
 use std::{
+    collections::{BTreeMap, HashMap},
+    convert::{TryFrom, TryInto},
     ffi::CStr,
     ffi::CString,
+    fmt::Display,
     iter,
     mem::size_of,
     ptr::{self, NonNull},
     sync::Arc,
 };
 
-use crate::array::ArrayData;
-use crate::buffer::Buffer;
-use crate::datatypes::{DataType, Field, TimeUnit};
+use crate::alloc::ALIGNMENT;
+use crate::array::{
+    make_array, Array, ArrayData, ArrayRef, DictionaryArray, GenericListArray,
+    OffsetSizeTrait,
+};
+use crate::buffer::{Buffer, MutableBuffer};
+use crate::datatypes::{
+    ArrowNativeType, ArrowPrimitiveType, DataType, Field, IntervalUnit, Schema,
+    SchemaRef, TimeUnit,
+};
 use crate::error::{ArrowError, Result};
+use crate::ffi_stream::FFI_ArrowArrayStream;
+use crate::record_batch::{RecordBatch, RecordBatchReader};
 use crate::util::bit_util;
 
+// the number of exported [FFI_ArrowArray] and [FFI_ArrowSchema] that were not released yet
+#[cfg(feature = "ffi-leak-check")]
+static LIVE_ALLOCATIONS: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+/// Returns the number of [FFI_ArrowArray] and [FFI_ArrowSchema] (including children) exported
+/// by this crate that were not released yet. If this number is not zero after all of them
+/// were released, there is a memory leak.
+///
+/// The counter is global: it is only meaningful in single-threaded programs, such as tests
+/// run with `--test-threads 1`.
+#[cfg(feature = "ffi-leak-check")]
+pub fn live_allocations() -> usize {
+    LIVE_ALLOCATIONS.load(std::sync::atomic::Ordering::SeqCst)
+}
+
 #[allow(dead_code)]
 struct SchemaPrivateData {
     field: Field,
+    format: Arc<CString>,
+    name: Arc<CString>,
     children_ptr: Box<[*mut FFI_ArrowSchema]>,
+    // the schema of the values of a dictionary, or null
+    dictionary: *mut FFI_ArrowSchema,
+    // the encoded metadata of `field`, if any
+    metadata: Option<Box<[u8]>>,
+}
+
+/// A cache of the strings (formats and names) of exported schemas. Producers that export the
+/// same schema repeatedly can export it via a context, so that each string is only allocated
+/// once instead of on every export.
+///
+/// Cached strings are reference-counted: a string is only freed once the context and
+/// every schema using it are released.
+#[derive(Debug, Default)]
+pub struct ExportContext {
+    strings: HashMap<String, Arc<CString>>,
+}
+
+impl ExportContext {
+    /// creates a new, empty, [ExportContext]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // returns the cached `CString` of `value`, allocating it if not yet cached
+    fn cstring(&mut self, value: &str) -> Result<Arc<CString>> {
+        if let Some(cached) = self.strings.get(value) {
+            return Ok(cached.clone());
+        }
+        let cstring = Arc::new(to_cstring(value)?);
+        self.strings.insert(value.to_string(), cstring.clone());
+        Ok(cstring)
+    }
+
+    /// exports `field` to a new [FFI_ArrowSchema], as [FFI_ArrowSchema::try_new],
+    /// reusing the cached strings.
+    pub fn export_schema(&mut self, field: Field) -> Result<FFI_ArrowSchema> {
+        FFI_ArrowSchema::try_new_in(field, Some(self))
+    }
+
+    /// creates a new `ArrowArray`, as [ArrowArray::try_new], reusing the cached strings.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    pub unsafe fn export_array(&mut self, data: ArrayData) -> Result<ArrowArray> {
+        let field = Field::new("", data.data_type().clone(), true);
+        let array = Arc::new(FFI_ArrowArray::new(&data));
+        let schema = Arc::new(self.export_schema(field)?);
+        Ok(ArrowArray { array, schema })
+    }
 }
 
 /// ABI-compatible struct for `ArrowSchema` from C Data Interface
@@ -122,66 +200,138 @@ unsafe extern "C" fn release_schema(schema: *mut FFI_ArrowSchema) {
     let schema = &mut *schema;
 
     // take ownership back to release it.
-    CString::from_raw(schema.format as *mut std::os::raw::c_char);
-    CString::from_raw(schema.name as *mut std::os::raw::c_char);
+    // this also frees the metadata, if any
     let private = Box::from_raw(schema.private_data as *mut SchemaPrivateData);
     for child in private.children_ptr.iter() {
         let _ = Box::from_raw(*child);
     }
+    if !private.dictionary.is_null() {
+        let _ = Box::from_raw(private.dictionary);
+    }
 
     schema.release = None;
+    #[cfg(feature = "ffi-leak-check")]
+    LIVE_ALLOCATIONS.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+}
+
+// returns `value` as a nul-terminated string
+fn to_cstring(value: &str) -> Result<CString> {
+    CString::new(value).map_err(|_| {
+        ArrowError::CDataInterface(format!(
+            "The string \"{}\" cannot be exported, as it contains a nul byte",
+            value.escape_default()
+        ))
+    })
 }
 
 impl FFI_ArrowSchema {
-    /// create a new [`Ffi_ArrowSchema`]. This fails if the fields' [`DataType`] is not supported.
-    fn try_new(field: Field) -> Result<FFI_ArrowSchema> {
-        let format = to_format(field.data_type())?;
-        let name = field.name().clone();
+    /// create a new [`FFI_ArrowSchema`]. This fails if the fields' [`DataType`] is not supported.
+    pub fn try_new(field: Field) -> Result<FFI_ArrowSchema> {
+        Self::try_new_in(field, None)
+    }
+
+    // create a new [`FFI_ArrowSchema`] whose strings are cached in `context`, if any.
+    fn try_new_in(
+        field: Field,
+        mut context: Option<&mut ExportContext>,
+    ) -> Result<FFI_ArrowSchema> {
+        // a dictionary is exported with the format of its keys, and its values are exported
+        // via `dictionary`
+        let (format, dictionary) = match field.data_type() {
+            DataType::Dictionary(key_type, value_type) => {
+                let values = Field::new("", value_type.as_ref().clone(), true);
+                let dictionary =
+                    FFI_ArrowSchema::try_new_in(values, context.as_deref_mut())?;
+                (to_format(key_type)?, Some(Box::new(dictionary)))
+            }
+            data_type => (to_format(data_type)?, None),
+        };
 
         // allocate (and hold) the children
         let children_vec = match field.data_type() {
-            DataType::List(field) => {
-                vec![Box::new(FFI_ArrowSchema::try_new(field.as_ref().clone())?)]
-            }
-            DataType::LargeList(field) => {
-                vec![Box::new(FFI_ArrowSchema::try_new(field.as_ref().clone())?)]
+            DataType::List(field) => vec![Box::new(FFI_ArrowSchema::try_new_in(
+                field.as_ref().clone(),
+                context.as_deref_mut(),
+            )?)],
+            DataType::LargeList(field) => vec![Box::new(FFI_ArrowSchema::try_new_in(
+                field.as_ref().clone(),
+                context.as_deref_mut(),
+            )?)],
+            DataType::FixedSizeList(field, _) => {
+                vec![Box::new(FFI_ArrowSchema::try_new_in(
+                    field.as_ref().clone(),
+                    context.as_deref_mut(),
+                )?)]
             }
             DataType::Struct(fields) => fields
                 .iter()
-                .map(|field| Ok(Box::new(FFI_ArrowSchema::try_new(field.clone())?)))
+                .map(|field| {
+                    Ok(Box::new(FFI_ArrowSchema::try_new_in(
+                        field.clone(),
+                        context.as_deref_mut(),
+                    )?))
+                })
                 .collect::<Result<Vec<_>>>()?,
             _ => vec![],
         };
-        // note: this cannot be done along with the above because the above is fallible and this op leaks.
+        let (format, name) = match context {
+            Some(context) => (context.cstring(&format)?, context.cstring(field.name())?),
+            None => (
+                Arc::new(to_cstring(&format)?),
+                Arc::new(to_cstring(field.name())?),
+            ),
+        };
+
+        // note: this must be done after all fallible operations, as the children and the
+        // dictionary are only released by `release_schema` from here on.
         let children_ptr = children_vec
             .into_iter()
             .map(Box::into_raw)
             .collect::<Box<_>>();
         let n_children = children_ptr.len() as i64;
+        let dictionary = dictionary.map(Box::into_raw).unwrap_or_else(ptr::null_mut);
 
-        let flags = field.is_nullable() as i64 * 2;
+        let mut flags = field.is_nullable() as i64 * 2;
+        if field.dict_is_ordered() == Some(true) {
+            flags |= ARROW_FLAG_DICTIONARY_ORDERED;
+        }
+
+        let metadata = field.metadata().as_ref().map(|metadata| {
+            encode_entries(metadata.len(), metadata.iter()).into_boxed_slice()
+        });
 
         let mut private = Box::new(SchemaPrivateData {
             field,
+            format,
+            name,
             children_ptr,
+            dictionary,
+            metadata,
         });
 
+        #[cfg(feature = "ffi-leak-check")]
+        LIVE_ALLOCATIONS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
         // <https://arrow.apache.org/docs/format/CDataInterface.html#c.ArrowSchema>
         Ok(FFI_ArrowSchema {
-            format: CString::new(format).unwrap().into_raw(),
-            name: CString::new(name).unwrap().into_raw(),
-            metadata: std::ptr::null_mut(),
+            format: private.format.as_ptr(),
+            name: private.name.as_ptr(),
+            metadata: private
+                .metadata
+                .as_ref()
+                .map(|metadata| metadata.as_ptr() as *const ::std::os::raw::c_char)
+                .unwrap_or_else(ptr::null),
             flags,
             n_children,
             children: private.children_ptr.as_mut_ptr(),
-            dictionary: std::ptr::null_mut(),
+            dictionary: private.dictionary,
             release: Some(release_schema),
             private_data: Box::into_raw(private) as *mut ::std::os::raw::c_void,
         })
     }
 
     /// create an empty [FFI_ArrowSchema]
-    fn empty() -> Self {
+    pub(crate) fn empty() -> Self {
         Self {
             format: std::ptr::null_mut(),
             name: std::ptr::null_mut(),
@@ -196,12 +346,32 @@ impl FFI_ArrowSchema {
     }
 
     /// returns the format of this schema.
+    /// # Panic
+    /// This function panics if the format is null or not valid utf8. Use
+    /// [FFI_ArrowSchema::try_format] for schemas of untrusted producers.
     pub fn format(&self) -> &str {
-        assert!(!self.format.is_null());
+        self.try_format()
+            .expect("The external API has a null or non-utf8 format")
+    }
+
+    /// returns the format of this schema.
+    /// # Error
+    /// Errors if the format is null or not valid utf8.
+    pub fn try_format(&self) -> Result<&str> {
+        if self.format.is_null() {
+            return Err(ArrowError::CDataInterface(
+                "The format of the schema is null".to_string(),
+            ));
+        }
         // safe because the lifetime of `self.format` equals `self`
         unsafe { CStr::from_ptr(self.format) }
             .to_str()
-            .expect("The external API has a non-utf8 as format")
+            .map_err(|e| {
+                ArrowError::CDataInterface(format!(
+                    "The format of the schema is not valid utf8: {}",
+                    e
+                ))
+            })
     }
 
     /// returns the name of this schema.
@@ -217,9 +387,179 @@ impl FFI_ArrowSchema {
         unsafe { self.children.add(index).as_ref().unwrap().as_ref().unwrap() }
     }
 
+    /// returns the first child of this schema whose name is `name`.
+    /// Duplicate names are allowed by the specification: only the first match is returned.
+    /// # Error
+    /// Errors if no child is named `name`.
+    pub fn child_by_name(&self, name: &str) -> Result<&Self> {
+        (0..self.n_children as usize)
+            .map(|i| self.child(i))
+            .find(|child| child.name() == name)
+            .ok_or_else(|| {
+                ArrowError::CDataInterface(format!(
+                    "The schema \"{}\" has no child named \"{}\"",
+                    self.name(),
+                    name
+                ))
+            })
+    }
+
     pub fn nullable(&self) -> bool {
         (self.flags / 2) & 1 == 1
     }
+
+    /// returns the key/value metadata of this schema, or `None` if it has none.
+    /// # Error
+    /// Errors if the metadata is invalid, e.g. not valid utf8.
+    pub fn metadata(&self) -> Result<Option<BTreeMap<String, String>>> {
+        if self.metadata.is_null() {
+            return Ok(None);
+        }
+        // safe because the lifetime of `self.metadata` equals `self`
+        unsafe { decode_entries_from_ptr(self.metadata as *const u8) }.map(Some)
+    }
+
+    /// returns the name of the extension type of this schema, i.e. the value of
+    /// [EXTENSION_NAME_KEY] in its metadata, or `None` if it is not an extension type.
+    /// # Error
+    /// Errors if the metadata is invalid, e.g. not valid utf8.
+    pub fn extension_name(&self) -> Result<Option<String>> {
+        Ok(self
+            .metadata()?
+            .and_then(|mut metadata| metadata.remove(EXTENSION_NAME_KEY)))
+    }
+
+    /// whether this schema is of the canonical JSON extension type, [JSON_EXTENSION_NAME],
+    /// i.e. whether its strings are JSON documents.
+    /// # Error
+    /// Errors if the metadata is invalid, or if the extension type is JSON but the storage
+    /// type is neither `Utf8` nor `LargeUtf8`.
+    pub fn is_json(&self) -> Result<bool> {
+        if self.extension_name()?.as_deref() != Some(JSON_EXTENSION_NAME) {
+            return Ok(false);
+        }
+        match self.try_format()? {
+            "u" | "U" => Ok(true),
+            format => Err(ArrowError::CDataInterface(format!(
+                "The storage type of the extension type \"{}\" of \"{}\" must be a string, \
+                but its format is \"{}\"",
+                JSON_EXTENSION_NAME,
+                self.name(),
+                format
+            ))),
+        }
+    }
+}
+
+/// The key of the metadata of a field that holds the name of its extension type.
+/// See <https://arrow.apache.org/docs/format/Columnar.html#extension-types>
+pub const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+/// The name of the canonical extension type of JSON documents, stored as `Utf8` or `LargeUtf8`.
+/// See <https://arrow.apache.org/docs/format/CanonicalExtensions.html#json>
+pub const JSON_EXTENSION_NAME: &str = "arrow.json";
+
+/// Encodes `metadata` as [FFI_ArrowSchema]'s metadata is specified by the C Data Interface:
+/// the number of entries, followed by the length and bytes of each key and value. All
+/// integers are int32 in native endianness.
+/// See <https://arrow.apache.org/docs/format/CDataInterface.html#c.ArrowSchema.metadata>
+pub fn encode_metadata(metadata: &HashMap<String, String>) -> Vec<u8> {
+    encode_entries(metadata.len(), metadata.iter())
+}
+
+/// Decodes metadata encoded as in [encode_metadata]. A null `ptr` is decoded as an empty map.
+/// # Safety
+/// `ptr` must be null or point to valid encoded metadata: since its length is not known,
+/// a truncated buffer is read out of bounds. See [decode_metadata_slice].
+/// # Error
+/// Errors if a length is negative or if a key or value is not valid utf8.
+pub unsafe fn decode_metadata(
+    ptr: *const ::std::os::raw::c_char,
+) -> Result<HashMap<String, String>> {
+    if ptr.is_null() {
+        return Ok(HashMap::new());
+    }
+    decode_entries_from_ptr(ptr as *const u8)
+}
+
+/// Decodes metadata encoded as in [encode_metadata] from `bytes`.
+/// # Error
+/// Errors if `bytes` is truncated, if a length is negative or if a key or value is not
+/// valid utf8.
+pub fn decode_metadata_slice(bytes: &[u8]) -> Result<HashMap<String, String>> {
+    decode_entries(|offset, len| {
+        offset
+            .checked_add(len)
+            .and_then(|end| bytes.get(offset..end))
+            .ok_or_else(|| {
+                ArrowError::CDataInterface(format!(
+                    "The metadata is truncated: {} bytes were expected at offset {}, \
+                    but it has {} bytes",
+                    len,
+                    offset,
+                    bytes.len()
+                ))
+            })
+    })
+}
+
+fn encode_entries<'a>(
+    n_entries: usize,
+    entries: impl Iterator<Item = (&'a String, &'a String)>,
+) -> Vec<u8> {
+    let mut buffer = (n_entries as i32).to_ne_bytes().to_vec();
+    for (key, value) in entries {
+        buffer.extend_from_slice(&(key.len() as i32).to_ne_bytes());
+        buffer.extend_from_slice(key.as_bytes());
+        buffer.extend_from_slice(&(value.len() as i32).to_ne_bytes());
+        buffer.extend_from_slice(value.as_bytes());
+    }
+    buffer
+}
+
+// # Safety
+// `ptr` must point to valid encoded metadata.
+unsafe fn decode_entries_from_ptr<C: iter::FromIterator<(String, String)>>(
+    ptr: *const u8,
+) -> Result<C> {
+    decode_entries(|offset, len| Ok(std::slice::from_raw_parts(ptr.add(offset), len)))
+}
+
+// decodes the entries of encoded metadata, whose `len` bytes at `offset` are read via `read`
+fn decode_entries<'a, C: iter::FromIterator<(String, String)>>(
+    read: impl Fn(usize, usize) -> Result<&'a [u8]>,
+) -> Result<C> {
+    let read_len = |offset: &mut usize| {
+        let bytes = read(*offset, size_of::<i32>())?;
+        *offset += size_of::<i32>();
+        let len = i32::from_ne_bytes(bytes.try_into().unwrap());
+        if len < 0 {
+            return Err(ArrowError::CDataInterface(format!(
+                "The metadata has a negative length ({}) at offset {}",
+                len,
+                *offset - size_of::<i32>()
+            )));
+        }
+        Ok(len as usize)
+    };
+    let read_string = |offset: &mut usize| {
+        let len = read_len(offset)?;
+        let bytes = read(*offset, len)?;
+        *offset += len;
+        String::from_utf8(bytes.to_vec()).map_err(|_| {
+            ArrowError::CDataInterface("The metadata is not valid utf8".to_string())
+        })
+    };
+
+    let mut offset = 0;
+    let n_entries = read_len(&mut offset)?;
+    (0..n_entries)
+        .map(|_| {
+            let key = read_string(&mut offset)?;
+            let value = read_string(&mut offset)?;
+            Ok((key, value))
+        })
+        .collect()
 }
 
 impl Drop for FFI_ArrowSchema {
@@ -231,9 +571,151 @@ impl Drop for FFI_ArrowSchema {
     }
 }
 
+/// Exports a [Schema] as a struct whose children are its fields, and whose metadata is the
+/// schema's metadata. This is how a schema is exchanged without data, e.g. to negotiate it.
+impl TryFrom<&Schema> for FFI_ArrowSchema {
+    type Error = ArrowError;
+
+    fn try_from(schema: &Schema) -> Result<Self> {
+        let mut field = Field::new("", DataType::Struct(schema.fields().clone()), false);
+        // a schema without metadata is exported with a null `metadata`
+        if !schema.metadata().is_empty() {
+            field.set_metadata(Some(
+                schema
+                    .metadata()
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect(),
+            ));
+        }
+        FFI_ArrowSchema::try_new(field)
+    }
+}
+
+/// Imports a [Schema] from a struct, as exported via [FFI_ArrowSchema::try_from].
+impl TryFrom<&FFI_ArrowSchema> for Schema {
+    type Error = ArrowError;
+
+    fn try_from(schema: &FFI_ArrowSchema) -> Result<Self> {
+        let field = to_field(schema)?;
+        let fields = match field.data_type() {
+            DataType::Struct(fields) => fields.clone(),
+            data_type => {
+                return Err(ArrowError::CDataInterface(format!(
+                    "A schema must be imported from a struct, but it is {:?}",
+                    data_type
+                )))
+            }
+        };
+        let metadata = field
+            .metadata()
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        Ok(Schema::new_with_metadata(fields, metadata))
+    }
+}
+
 /// See https://arrow.apache.org/docs/format/CDataInterface.html#data-type-description-format-strings
-fn to_field(schema: &FFI_ArrowSchema) -> Result<Field> {
-    let data_type = match schema.format() {
+pub(crate) fn to_field(schema: &FFI_ArrowSchema) -> Result<Field> {
+    if !schema.dictionary.is_null() {
+        return to_dictionary_field(schema);
+    }
+    let data_type = match schema.try_format()? {
+        "+l" => {
+            let child = single_child(schema)?;
+            DataType::List(Box::new(to_field(child)?))
+        }
+        "+L" => {
+            let child = single_child(schema)?;
+            DataType::LargeList(Box::new(to_field(child)?))
+        }
+        other if other.starts_with("+w:") => {
+            let size = other[3..]
+                .parse::<i32>()
+                .ok()
+                .filter(|size| *size >= 0)
+                .ok_or_else(|| {
+                    ArrowError::CDataInterface(format!(
+                        "The fixed-size list format \"{}\" is invalid",
+                        other
+                    ))
+                })?;
+            let child = single_child(schema)?;
+            DataType::FixedSizeList(Box::new(to_field(child)?), size)
+        }
+        "+s" => {
+            let children = (0..schema.n_children as usize)
+                .map(|x| to_field(schema.child(x)))
+                .collect::<Result<Vec<_>>>()?;
+            DataType::Struct(children)
+        }
+        other => primitive_data_type(other)?,
+    };
+    let mut field = Field::new(schema.name(), data_type, schema.nullable());
+    field.set_metadata(schema.metadata()?);
+    Ok(field)
+}
+
+// returns the only child of `schema`, e.g. the values of a list
+fn single_child(schema: &FFI_ArrowSchema) -> Result<&FFI_ArrowSchema> {
+    if schema.n_children != 1 || schema.children.is_null() {
+        return Err(ArrowError::CDataInterface(format!(
+            "The schema with format \"{}\" must have 1 child, but it has {}",
+            schema.try_format()?,
+            schema.n_children
+        )));
+    }
+    Ok(schema.child(0))
+}
+
+// returns the field of a dictionary-encoded `schema`: its format is the format of the keys,
+// and its values are described by its `dictionary`
+fn to_dictionary_field(schema: &FFI_ArrowSchema) -> Result<Field> {
+    // safe because `dictionary` is not null and its lifetime equals `schema`
+    let dictionary = unsafe { &*schema.dictionary };
+    if !dictionary.dictionary.is_null() {
+        return Err(ArrowError::CDataInterface(format!(
+            "The values of the dictionary of \"{}\" are themselves dictionary-encoded, \
+            which is not allowed",
+            schema.name()
+        )));
+    }
+    let key_type = primitive_data_type(schema.try_format()?)?;
+    if !matches!(
+        key_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+    ) {
+        return Err(ArrowError::CDataInterface(format!(
+            "The keys of the dictionary of \"{}\" must be integers, but they are {:?}",
+            schema.name(),
+            key_type
+        )));
+    }
+    let value_type = to_field(dictionary)?.data_type().clone();
+
+    let mut field = Field::new_dict(
+        schema.name(),
+        DataType::Dictionary(Box::new(key_type), Box::new(value_type)),
+        schema.nullable(),
+        0,
+        schema.flags & ARROW_FLAG_DICTIONARY_ORDERED != 0,
+    );
+    field.set_metadata(schema.metadata()?);
+    Ok(field)
+}
+
+// returns the data type of a format without children
+fn primitive_data_type(format: &str) -> Result<DataType> {
+    Ok(match format {
         "n" => DataType::Null,
         "b" => DataType::Boolean,
         "c" => DataType::Int8,
@@ -257,28 +739,133 @@ fn to_field(schema: &FFI_ArrowSchema) -> Result<Field> {
         "ttm" => DataType::Time32(TimeUnit::Millisecond),
         "ttu" => DataType::Time64(TimeUnit::Microsecond),
         "ttn" => DataType::Time64(TimeUnit::Nanosecond),
-        "+l" => {
-            let child = schema.child(0);
-            DataType::List(Box::new(to_field(child)?))
-        }
-        "+L" => {
-            let child = schema.child(0);
-            DataType::LargeList(Box::new(to_field(child)?))
+        "tDs" => DataType::Duration(TimeUnit::Second),
+        "tDm" => DataType::Duration(TimeUnit::Millisecond),
+        "tDu" => DataType::Duration(TimeUnit::Microsecond),
+        "tDn" => DataType::Duration(TimeUnit::Nanosecond),
+        "tiM" => DataType::Interval(IntervalUnit::YearMonth),
+        "tiD" => DataType::Interval(IntervalUnit::DayTime),
+        other if other.starts_with("d:") => decimal_data_type(other)?,
+        other if other.starts_with("w:") => {
+            // a width that is not positive would make the length of the values invalid
+            let byte_width = other[2..]
+                .parse::<i32>()
+                .ok()
+                .filter(|byte_width| *byte_width > 0)
+                .ok_or_else(|| {
+                    ArrowError::CDataInterface(format!(
+                        "The fixed-size binary format \"{}\" is invalid",
+                        other
+                    ))
+                })?;
+            DataType::FixedSizeBinary(byte_width)
         }
-        "+s" => {
-            let children = (0..schema.n_children as usize)
-                .map(|x| to_field(schema.child(x)))
-                .collect::<Result<Vec<_>>>()?;
-            DataType::Struct(children)
-        }
-        other => {
-            return Err(ArrowError::CDataInterface(format!(
-                "The datatype \"{:?}\" is still not supported in Rust implementation",
-                other
-            )))
+        other => match timestamp_data_type(other) {
+            Some(data_type) => data_type,
+            None => {
+                return Err(ArrowError::CDataInterface(format!(
+                    "The datatype \"{:?}\" is still not supported in Rust implementation",
+                    other
+                )))
+            }
+        },
+    })
+}
+
+// returns the data type of a decimal format, `d:<precision>,<scale>`, optionally followed
+// by the bit width, which must be 128
+fn decimal_data_type(format: &str) -> Result<DataType> {
+    let parts = format[2..]
+        .split(',')
+        .map(|part| part.parse::<usize>())
+        .collect::<std::result::Result<Vec<_>, _>>();
+    match parts.as_deref() {
+        Ok([precision, scale]) | Ok([precision, scale, 128]) => {
+            Ok(DataType::Decimal(*precision, *scale))
         }
+        _ => Err(ArrowError::CDataInterface(format!(
+            "The decimal format \"{}\" is invalid or not supported",
+            format
+        ))),
+    }
+}
+
+// returns the data type of a timestamp format, `ts{s,m,u,n}:` followed by the timezone
+fn timestamp_data_type(format: &str) -> Option<DataType> {
+    let mut parts = format.splitn(2, ':');
+    let unit = match parts.next()? {
+        "tss" => TimeUnit::Second,
+        "tsm" => TimeUnit::Millisecond,
+        "tsu" => TimeUnit::Microsecond,
+        "tsn" => TimeUnit::Nanosecond,
+        _ => return None,
     };
-    Ok(Field::new(schema.name(), data_type, schema.nullable()))
+    // the timezone is empty when there is none, but the colon is mandatory
+    let timezone = parts.next()?;
+    let timezone = if timezone.is_empty() {
+        None
+    } else {
+        Some(timezone.to_string())
+    };
+    Some(DataType::Timestamp(unit, timezone))
+}
+
+/// Checks whether every column of `schema` has a data type supported by this implementation.
+/// When `schema` is a struct (e.g. an exported record batch), each of its children is a column;
+/// otherwise, `schema` itself is the single column.
+/// # Error
+/// Errors with the `(name, format)` of every unsupported column, so that they can all be
+/// reported at once instead of failing on the first one.
+pub fn check_schema_supported(
+    schema: &FFI_ArrowSchema,
+) -> std::result::Result<(), Vec<(String, String)>> {
+    // an invalid format is reported as an unsupported column below
+    let columns = if schema.try_format().ok() == Some("+s") {
+        (0..schema.n_children as usize)
+            .map(|i| schema.child(i))
+            .collect::<Vec<_>>()
+    } else {
+        vec![schema]
+    };
+
+    let unsupported = columns
+        .into_iter()
+        .filter(|column| to_field(column).is_err())
+        .map(|column| {
+            let format = column
+                .try_format()
+                .map(|format| format.to_string())
+                .unwrap_or_else(|error| error.to_string());
+            (column.name().to_string(), format)
+        })
+        .collect::<Vec<_>>();
+
+    if unsupported.is_empty() {
+        Ok(())
+    } else {
+        Err(unsupported)
+    }
+}
+
+// returns a suggestion of an exportable alternative to `data_type`, which `to_format` rejects
+fn export_suggestion(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Time32(unit) => format!(
+            "Time32 only has second and millisecond units: consider casting to Time64({:?})",
+            unit
+        ),
+        DataType::Time64(unit) => format!(
+            "Time64 only has microsecond and nanosecond units: consider casting to Time32({:?})",
+            unit
+        ),
+        DataType::Union(_) => "consider exporting each of its children as a separate array"
+            .to_string(),
+        DataType::Dictionary(_, value_type) => format!(
+            "consider exporting it as a field, or casting it to {:?}",
+            value_type
+        ),
+        _ => "consider casting it to Utf8".to_string(),
+    }
 }
 
 /// See https://arrow.apache.org/docs/format/CDataInterface.html#data-type-description-format-strings
@@ -307,19 +894,256 @@ fn to_format(data_type: &DataType) -> Result<String> {
         DataType::Time32(TimeUnit::Millisecond) => "ttm",
         DataType::Time64(TimeUnit::Microsecond) => "ttu",
         DataType::Time64(TimeUnit::Nanosecond) => "ttn",
+        DataType::Duration(TimeUnit::Second) => "tDs",
+        DataType::Duration(TimeUnit::Millisecond) => "tDm",
+        DataType::Duration(TimeUnit::Microsecond) => "tDu",
+        DataType::Duration(TimeUnit::Nanosecond) => "tDn",
+        DataType::Interval(IntervalUnit::YearMonth) => "tiM",
+        DataType::Interval(IntervalUnit::DayTime) => "tiD",
+        DataType::FixedSizeBinary(byte_width) => return Ok(format!("w:{}", byte_width)),
+        DataType::FixedSizeList(_, size) => return Ok(format!("+w:{}", size)),
+        DataType::Decimal(precision, scale) => {
+            return Ok(format!("d:{},{}", precision, scale));
+        }
+        DataType::Timestamp(unit, timezone) => {
+            let unit = match unit {
+                TimeUnit::Second => "s",
+                TimeUnit::Millisecond => "m",
+                TimeUnit::Microsecond => "u",
+                TimeUnit::Nanosecond => "n",
+            };
+            return Ok(format!(
+                "ts{}:{}",
+                unit,
+                timezone.as_deref().unwrap_or_default()
+            ));
+        }
         DataType::List(_) => "+l",
         DataType::LargeList(_) => "+L",
         DataType::Struct(_) => "+s",
         z => {
             return Err(ArrowError::CDataInterface(format!(
-                "The datatype \"{:?}\" is still not supported in Rust implementation",
-                z
+                "The datatype \"{:?}\" is still not supported in Rust implementation; {}",
+                z,
+                export_suggestion(z)
             )))
         }
     }
     .to_string())
 }
 
+/// The kind of a buffer of the C data interface, see [BufferSpec].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferKind {
+    /// a bitmap of the valid slots
+    Validity,
+    /// the offsets of variable-sized slots into the data buffer or the child
+    Offset,
+    /// the values of the slots
+    Data,
+    /// the type ids of the slots of a union
+    Types,
+    /// the sizes of the slots of a list view
+    Sizes,
+}
+
+/// The specification of a buffer of the C data interface, as returned by [buffer_layout].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferSpec {
+    /// the name of the buffer, e.g. "validity"
+    pub name: &'static str,
+    /// the number of bits of each slot of the buffer
+    pub bit_width: usize,
+    /// the kind of the buffer
+    pub kind: BufferKind,
+}
+
+/// Returns the buffers (including the validity buffer) that an array with the format string
+/// `format` is expected to have in the C data interface, in order.
+/// The children of nested formats (e.g. `"+l"`) are not part of the layout.
+/// # Error
+/// Errors if the format is not supported.
+pub fn buffer_layout(format: &str) -> Result<Vec<BufferSpec>> {
+    let data_type = match format {
+        // the children do not change the layout of their parent
+        "+l" => DataType::List(Box::new(Field::new("", DataType::Null, true))),
+        "+L" => DataType::LargeList(Box::new(Field::new("", DataType::Null, true))),
+        "+s" => DataType::Struct(vec![]),
+        other if other.starts_with("+w:") => {
+            DataType::FixedSizeList(Box::new(Field::new("", DataType::Null, true)), 0)
+        }
+        other => primitive_data_type(other)?,
+    };
+    if data_type == DataType::Null {
+        return Ok(vec![]);
+    }
+    let n_buffers = expected_n_buffers(&data_type).ok_or_else(|| {
+        ArrowError::CDataInterface(format!(
+            "The layout of the format \"{}\" is unknown",
+            format
+        ))
+    })?;
+    let has_offsets = matches!(
+        data_type,
+        DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Binary
+            | DataType::LargeBinary
+            | DataType::List(_)
+            | DataType::LargeList(_)
+    );
+
+    (0..n_buffers)
+        .map(|i| {
+            let (name, kind) = match i {
+                0 => ("validity", BufferKind::Validity),
+                1 if has_offsets => ("offsets", BufferKind::Offset),
+                _ => ("data", BufferKind::Data),
+            };
+            Ok(BufferSpec {
+                name,
+                bit_width: bit_width(&data_type, i)?,
+                kind,
+            })
+        })
+        .collect()
+}
+
+// returns the number of buffers (including the validity buffer) that an array of `data_type`
+// is expected to have in the C data interface, or `None` if it is not known.
+// This is set by the Arrow specification
+fn expected_n_buffers(data_type: &DataType) -> Option<usize> {
+    Some(match data_type {
+        DataType::Null => 0,
+        DataType::Boolean
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::Float16
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Date32
+        | DataType::Date64
+        | DataType::Time32(_)
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_)
+        | DataType::Interval(_)
+        | DataType::Decimal(_, _)
+        | DataType::FixedSizeBinary(_)
+        | DataType::Dictionary(_, _) => 2,
+        DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Binary
+        | DataType::LargeBinary => 3,
+        DataType::List(_) | DataType::LargeList(_) => 2,
+        DataType::Struct(_) | DataType::FixedSizeList(_, _) => 1,
+        _ => return None,
+    })
+}
+
+// checks that an imported array of `data_type` has as many buffers as the specification
+// sets. Types whose number of buffers is not known are not checked.
+fn validate_n_buffers(data_type: &DataType, n_buffers: i64) -> Result<()> {
+    match expected_n_buffers(data_type) {
+        Some(expected) if n_buffers != expected as i64 => {
+            Err(ArrowError::CDataInterface(format!(
+                "An array of type {:?} must have {} buffers (including the validity buffer), \
+                but the imported array has {}",
+                data_type, expected, n_buffers
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+// checks that `array` and `schema` are consistent with each other and with the specification
+fn validate_exported(array: &FFI_ArrowArray, schema: &FFI_ArrowSchema) -> Result<()> {
+    let field = to_field(schema)?;
+    let invalid = |reason: String| {
+        Err(ArrowError::CDataInterface(format!(
+            "The exported array \"{}\" of type {:?} is invalid: {}",
+            field.name(),
+            field.data_type(),
+            reason
+        )))
+    };
+
+    if array.release.is_none() || schema.release.is_none() {
+        return invalid("it was released".to_string());
+    }
+    if array.length < 0 || array.offset < 0 {
+        return invalid(format!(
+            "its length ({}) and offset ({}) must be non-negative",
+            array.length, array.offset
+        ));
+    }
+    if array.null_count > array.length {
+        return invalid(format!(
+            "its null count ({}) is larger than its length ({})",
+            array.null_count, array.length
+        ));
+    }
+    if array.null_count > 0 && !field.is_nullable() {
+        return invalid(format!(
+            "it has {} nulls but is not nullable",
+            array.null_count
+        ));
+    }
+    if let Some(n_buffers) = expected_n_buffers(field.data_type()) {
+        if array.n_buffers != n_buffers as i64 {
+            return invalid(format!(
+                "it has {} buffers, but {} were expected",
+                array.n_buffers, n_buffers
+            ));
+        }
+    }
+    if array.n_buffers > 0 {
+        if array.buffers.is_null() {
+            return invalid("its buffers are null".to_string());
+        }
+        let buffers = array.buffers as *const *const u8;
+        // safe because `buffers` has `n_buffers` entries
+        if array.null_count > 0 && unsafe { *buffers }.is_null() {
+            return invalid("it has nulls but its validity buffer is null".to_string());
+        }
+        if let Some(i) =
+            (1..array.n_buffers as usize).find(|i| unsafe { *buffers.add(*i) }.is_null())
+        {
+            return invalid(format!("its buffer at position {} is null", i));
+        }
+    }
+    if array.n_children != schema.n_children {
+        return invalid(format!(
+            "it has {} children, but its schema has {}",
+            array.n_children, schema.n_children
+        ));
+    }
+    (0..array.n_children as usize).try_for_each(|i| {
+        // safe because `children` has `n_children` non-null entries
+        let child = unsafe { &**array.children.add(i) };
+        validate_exported(child, schema.child(i))
+    })?;
+    match (array.dictionary.is_null(), schema.dictionary.is_null()) {
+        (true, true) => Ok(()),
+        // safe because both dictionaries are non-null
+        (false, false) => validate_exported(unsafe { &*array.dictionary }, unsafe {
+            &*schema.dictionary
+        }),
+        (false, true) => {
+            invalid("it has a dictionary, but its schema has none".to_string())
+        }
+        (true, false) => {
+            invalid("its schema has a dictionary, but it has none".to_string())
+        }
+    }
+}
+
 // returns the number of bits that buffer `i` (in the C data interface) is expected to have.
 // This is set by the Arrow specification
 fn bit_width(data_type: &DataType, i: usize) -> Result<usize> {
@@ -334,10 +1158,13 @@ fn bit_width(data_type: &DataType, i: usize) -> Result<usize> {
         (DataType::UInt64, 1) => size_of::<u64>() * 8,
         (DataType::Int8, 1) => size_of::<i8>() * 8,
         (DataType::Int16, 1) => size_of::<i16>() * 8,
-        (DataType::Int32, 1) | (DataType::Date32, 1) | (DataType::Time32(_), 1) => size_of::<i32>() * 8,
-        (DataType::Int64, 1) | (DataType::Date64, 1) | (DataType::Time64(_), 1) => size_of::<i64>() * 8,
+        (DataType::Int32, 1) | (DataType::Date32, 1) | (DataType::Time32(_), 1) | (DataType::Interval(IntervalUnit::YearMonth), 1) => size_of::<i32>() * 8,
+        (DataType::Int64, 1) | (DataType::Date64, 1) | (DataType::Time64(_), 1) | (DataType::Timestamp(_, _), 1) | (DataType::Duration(_), 1) | (DataType::Interval(IntervalUnit::DayTime), 1) => size_of::<i64>() * 8,
+        (DataType::Float16, 1) => size_of::<u16>() * 8,
         (DataType::Float32, 1) => size_of::<f32>() * 8,
         (DataType::Float64, 1) => size_of::<f64>() * 8,
+        (DataType::Decimal(_, _), 1) => size_of::<i128>() * 8,
+        (DataType::FixedSizeBinary(byte_width), 1) => *byte_width as usize * 8,
         // primitive types have a single buffer
         (DataType::Boolean, _) |
         (DataType::UInt8, _) |
@@ -348,8 +1175,13 @@ fn bit_width(data_type: &DataType, i: usize) -> Result<usize> {
         (DataType::Int16, _) |
         (DataType::Int32, _) | (DataType::Date32, _) | (DataType::Time32(_), _) |
         (DataType::Int64, _) | (DataType::Date64, _) | (DataType::Time64(_), _) |
+        (DataType::Timestamp(_, _), _) | (DataType::Duration(_), _) |
+        (DataType::Interval(_), _) |
+        (DataType::Float16, _) |
         (DataType::Float32, _) |
-        (DataType::Float64, _) => {
+        (DataType::Float64, _) |
+        (DataType::Decimal(_, _), _) |
+        (DataType::FixedSizeBinary(_), _) => {
             return Err(ArrowError::CDataInterface(format!(
                 "The datatype \"{:?}\" expects 2 buffers, but requested {}. Please verify that the C data interface is correctly implemented.",
                 data_type, i
@@ -375,6 +1207,8 @@ fn bit_width(data_type: &DataType, i: usize) -> Result<usize> {
                 data_type, i
             )))
         }
+        // dictionaries have the buffers of their keys
+        (DataType::Dictionary(key_type, _), _) => return bit_width(key_type, i),
         _ => {
             return Err(ArrowError::CDataInterface(format!(
                 "The datatype \"{:?}\" is still not supported in Rust implementation",
@@ -387,6 +1221,17 @@ fn bit_width(data_type: &DataType, i: usize) -> Result<usize> {
 /// ABI-compatible struct for ArrowArray from C Data Interface
 /// See <https://arrow.apache.org/docs/format/CDataInterface.html#structure-definitions>
 /// This was created by bindgen
+///
+/// # Buffers
+/// Arrays exported by this crate have their buffers in the order set by the specification,
+/// which does not change between versions: the validity buffer first (a null pointer when the
+/// array has no validity), followed by the buffers of its type:
+/// * `Null`: no buffers, not even a validity buffer
+/// * `Boolean`, primitive types, `Decimal` and `FixedSizeBinary`: `[validity, values]`
+/// * `Utf8`, `LargeUtf8`, `Binary` and `LargeBinary`: `[validity, offsets, values]`
+/// * `List` and `LargeList`: `[validity, offsets]`, with the values as its only child
+/// * `Struct` and `FixedSizeList`: `[validity]`, with the values as children
+/// * `Dictionary`: the buffers of its keys, with the values exported as `dictionary`
 #[repr(C)]
 #[derive(Debug)]
 pub struct FFI_ArrowArray {
@@ -398,7 +1243,8 @@ pub struct FFI_ArrowArray {
     pub(crate) buffers: *mut *const ::std::os::raw::c_void,
     children: *mut *mut FFI_ArrowArray,
     dictionary: *mut FFI_ArrowArray,
-    release: ::std::option::Option<unsafe extern "C" fn(arg1: *mut FFI_ArrowArray)>,
+    pub(crate) release:
+        ::std::option::Option<unsafe extern "C" fn(arg1: *mut FFI_ArrowArray)>,
     // When exported, this MUST contain everything that is owned by this array.
     // for example, any buffer pointed to in `buffers` must be here, as well as the `buffers` pointer
     // itself.
@@ -407,8 +1253,37 @@ pub struct FFI_ArrowArray {
     private_data: *mut ::std::os::raw::c_void,
 }
 
+// the addresses of the arrays imported via [ArrowArray::try_from_raw] that were not dropped
+// yet, used to detect double imports in debug builds.
+#[cfg(debug_assertions)]
+lazy_static::lazy_static! {
+    static ref IMPORTED_ARRAYS: std::sync::Mutex<std::collections::HashSet<usize>> =
+        std::sync::Mutex::new(std::collections::HashSet::new());
+}
+
+// registers `array` as imported, returning `false` if it already was.
+#[cfg(debug_assertions)]
+fn register_import(array: *const FFI_ArrowArray) -> bool {
+    IMPORTED_ARRAYS.lock().unwrap().insert(array as usize)
+}
+
+#[cfg(not(debug_assertions))]
+fn register_import(_: *const FFI_ArrowArray) -> bool {
+    true
+}
+
+// unregisters `array` as imported, e.g. because it is dropped.
+#[cfg(debug_assertions)]
+fn unregister_import(array: *const FFI_ArrowArray) {
+    IMPORTED_ARRAYS.lock().unwrap().remove(&(array as usize));
+}
+
+#[cfg(not(debug_assertions))]
+fn unregister_import(_: *const FFI_ArrowArray) {}
+
 impl Drop for FFI_ArrowArray {
     fn drop(&mut self) {
+        unregister_import(self);
         match self.release {
             None => (),
             Some(release) => unsafe { release(self) },
@@ -424,10 +1299,84 @@ unsafe extern "C" fn release_array(array: *mut FFI_ArrowArray) {
     let array = &mut *array;
 
     // take ownership of `private_data`, therefore dropping it`
-    let private = Box::from_raw(array.private_data as *mut PrivateData);
+    let mut private = Box::from_raw(array.private_data as *mut PrivateData);
+    if let Some(on_release) = private.on_release.take() {
+        // a panic cannot unwind through the consumer's call of this callback
+        if std::panic::catch_unwind(std::panic::AssertUnwindSafe(on_release)).is_err() {
+            std::process::abort();
+        }
+    }
     for child in private.children.iter() {
         let _ = Box::from_raw(*child);
     }
+    if !private.dictionary.is_null() {
+        let _ = Box::from_raw(private.dictionary);
+    }
+
+    array.release = None;
+    #[cfg(feature = "ffi-leak-check")]
+    LIVE_ALLOCATIONS.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+}
+
+/// An imported [FFI_ArrowArray] whose release was deferred via [ArrowArray::defer_release].
+/// Dropping it calls the producer's release callback on the current thread.
+#[derive(Debug)]
+pub struct DeferredRelease(FFI_ArrowArray);
+
+// the purpose of this struct is to move the producer's array to the thread that must release it
+unsafe impl Send for DeferredRelease {}
+
+struct DeferredPrivateData {
+    // the producer's array, whose release callback has not been called
+    array: FFI_ArrowArray,
+    sink: Box<dyn FnOnce(DeferredRelease) + Send>,
+}
+
+// callback used to hand the producer's array over to the sink instead of releasing it
+unsafe extern "C" fn release_deferred(array: *mut FFI_ArrowArray) {
+    if array.is_null() {
+        return;
+    }
+    let array = &mut *array;
+
+    let private = Box::from_raw(array.private_data as *mut DeferredPrivateData);
+    let DeferredPrivateData { array: inner, sink } = *private;
+    sink(DeferredRelease(inner));
+
+    array.release = None;
+}
+
+/// The owner of the memory of an imported array, which is released after the array is
+/// released, e.g. to unmap the shared memory segment that contains its buffers.
+/// See [ArrowArray::with_owner].
+///
+/// The owner is `Send` because it is released on whichever thread drops the last buffer
+/// imported from the array.
+pub trait ImportOwner: Send {
+    /// releases this owner. It is called once, after the producer's array is released.
+    fn release(self: Box<Self>);
+}
+
+struct OwnedPrivateData {
+    // the producer's array, whose release callback has not been called
+    array: FFI_ArrowArray,
+    owner: Box<dyn ImportOwner>,
+}
+
+// callback used to release the producer's array and then its owner
+unsafe extern "C" fn release_owned(array: *mut FFI_ArrowArray) {
+    if array.is_null() {
+        return;
+    }
+    let array = &mut *array;
+
+    let private = Box::from_raw(array.private_data as *mut OwnedPrivateData);
+    let OwnedPrivateData {
+        array: inner,
+        owner,
+    } = *private;
+    drop(inner);
+    owner.release();
 
     array.release = None;
 }
@@ -436,6 +1385,10 @@ struct PrivateData {
     buffers: Vec<Option<Buffer>>,
     buffers_ptr: Box<[*const std::os::raw::c_void]>,
     children: Box<[*mut FFI_ArrowArray]>,
+    // the values of a dictionary, or null
+    dictionary: *mut FFI_ArrowArray,
+    // called when the consumer releases the array
+    on_release: Option<Box<dyn FnOnce() + Send>>,
 }
 
 impl FFI_ArrowArray {
@@ -443,12 +1396,24 @@ impl FFI_ArrowArray {
     /// # Safety
     /// This method releases `buffers`. Consumers of this struct *must* call `release` before
     /// releasing this struct, or contents in `buffers` leak.
-    fn new(data: &ArrayData) -> Self {
+    pub(crate) fn new(data: &ArrayData) -> Self {
+        // a null count larger than the length is a bug of whoever built `data`
+        debug_assert!(
+            data.null_count() <= data.len(),
+            "The array has {} nulls, but its length is {}",
+            data.null_count(),
+            data.len()
+        );
         // * insert the null buffer at the start
         // * make all others `Option<Buffer>`.
-        let buffers = iter::once(data.null_buffer().cloned())
-            .chain(data.buffers().iter().map(|b| Some(b.clone())))
-            .collect::<Vec<_>>();
+        // the null type has no buffers, not even a validity buffer
+        let buffers = if data.data_type() == &DataType::Null {
+            vec![]
+        } else {
+            iter::once(data.null_buffer().cloned())
+                .chain(data.buffers().iter().map(|b| Some(b.clone())))
+                .collect::<Vec<_>>()
+        };
         let n_buffers = buffers.len() as i64;
 
         let buffers_ptr = buffers
@@ -460,12 +1425,20 @@ impl FFI_ArrowArray {
             })
             .collect::<Box<[_]>>();
 
-        let children = data
-            .child_data()
+        // the values of a dictionary are its first child in Rust, but are exported via
+        // `dictionary` instead of as a child
+        let (child_data, dictionary) = match data.data_type() {
+            DataType::Dictionary(_, _) => (&[][..], data.child_data().first()),
+            _ => (data.child_data(), None),
+        };
+        let children = child_data
             .iter()
             .map(|child| Box::into_raw(Box::new(FFI_ArrowArray::new(child))))
             .collect::<Box<_>>();
         let n_children = children.len() as i64;
+        let dictionary = dictionary
+            .map(|values| Box::into_raw(Box::new(FFI_ArrowArray::new(values))))
+            .unwrap_or_else(ptr::null_mut);
 
         // create the private data owning everything.
         // any other data must be added here, e.g. via a struct, to track lifetime.
@@ -473,8 +1446,13 @@ impl FFI_ArrowArray {
             buffers,
             buffers_ptr,
             children,
+            dictionary,
+            on_release: None,
         });
 
+        #[cfg(feature = "ffi-leak-check")]
+        LIVE_ALLOCATIONS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
         Self {
             length: data.len() as i64,
             null_count: data.null_count() as i64,
@@ -483,14 +1461,34 @@ impl FFI_ArrowArray {
             n_children,
             buffers: private_data.buffers_ptr.as_mut_ptr(),
             children: private_data.children.as_mut_ptr(),
-            dictionary: std::ptr::null_mut(),
+            dictionary: private_data.dictionary,
             release: Some(release_array),
             private_data: Box::into_raw(private_data) as *mut ::std::os::raw::c_void,
         }
     }
 
+    // returns a copy of this array that is released via `release`, and whose `private_data`
+    // is null, so that it can wrap this array
+    fn with_release(
+        &self,
+        release: unsafe extern "C" fn(arg1: *mut FFI_ArrowArray),
+    ) -> Self {
+        Self {
+            length: self.length,
+            null_count: self.null_count,
+            offset: self.offset,
+            n_buffers: self.n_buffers,
+            n_children: self.n_children,
+            buffers: self.buffers,
+            children: self.children,
+            dictionary: self.dictionary,
+            release: Some(release),
+            private_data: std::ptr::null_mut(),
+        }
+    }
+
     // create an empty `FFI_ArrowArray`, which can be used to import data into
-    fn empty() -> Self {
+    pub(crate) fn empty() -> Self {
         Self {
             length: 0,
             null_count: 0,
@@ -550,6 +1548,83 @@ unsafe fn create_buffer(
     NonNull::new(ptr as *mut u8).map(|ptr| Buffer::from_unowned(ptr, len, owner))
 }
 
+/// returns the last value of the offset buffer (buffer 1) of `array`, which has
+/// `offset + length + 1` offsets of type `T`.
+/// # Error
+/// Errors if the offset buffer is null or not aligned for `T`, or if the last offset is not a
+/// valid length.
+/// # Safety
+/// This function assumes that the offset buffer has `offset + length + 1` offsets.
+unsafe fn last_offset<T: Copy + Display + TryInto<usize>>(
+    array: &FFI_ArrowArray,
+) -> Result<usize> {
+    if array.length < 0 || array.offset < 0 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The length ({}) or the offset ({}) of the array is negative.",
+            array.length, array.offset
+        )));
+    }
+    if array.buffers.is_null() || array.n_buffers < 2 {
+        return Err(ArrowError::CDataInterface(
+            "The array has no offset buffer.".to_string(),
+        ));
+    }
+    // first buffer is the null buffer => add(1)
+    #[allow(clippy::cast_ptr_alignment)]
+    let offset_buffer = *(array.buffers as *mut *const u8).add(1) as *const T;
+    if offset_buffer.is_null() {
+        return Err(ArrowError::CDataInterface(
+            "The offset buffer (buffer 1) is null.".to_string(),
+        ));
+    }
+    // reading a misaligned pointer is undefined behavior
+    if (offset_buffer as usize) % std::mem::align_of::<T>() != 0 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The offset buffer at {:p} is not aligned to the {} bytes of its offsets ({})",
+            offset_buffer,
+            std::mem::align_of::<T>(),
+            std::any::type_name::<T>()
+        )));
+    }
+    // the last offset is at index `offset + length`
+    let last = *offset_buffer.add(array.offset() + array.len());
+    last.try_into().map_err(|_| {
+        ArrowError::CDataInterface(format!(
+            "The last offset of the array ({}) is not a valid length.",
+            last
+        ))
+    })
+}
+
+// errors if the buffer 1 of `array`, i.e. its values or offsets, is not aligned to their
+// native type: they are read as such, and reading a misaligned pointer is undefined behavior.
+fn check_values_alignment(
+    array: &FFI_ArrowArray,
+    data_type: &DataType,
+    index: usize,
+) -> Result<()> {
+    if index != 1 || array.buffers.is_null() || array.n_buffers < 2 {
+        return Ok(());
+    }
+    let alignment = match data_type {
+        // read byte by byte
+        DataType::FixedSizeBinary(_) | DataType::Decimal(_, _) => 1,
+        _ => match bit_width(data_type, 1) {
+            Ok(bits) if bits == 16 || bits == 32 || bits == 64 => bits / 8,
+            _ => 1,
+        },
+    };
+    // safe because `buffers` has at least 2 entries
+    let ptr = unsafe { *(array.buffers as *const *const u8).add(1) };
+    if (ptr as usize) % alignment != 0 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The buffer 1 at {:p} is not aligned to the {} bytes of the values of {:?}",
+            ptr, alignment, data_type
+        )));
+    }
+    Ok(())
+}
+
 fn create_child(
     owner: Arc<FFI_ArrowArray>,
     array: &FFI_ArrowArray,
@@ -570,42 +1645,449 @@ fn create_child(
     }
 }
 
+fn create_dictionary(
+    owner: Arc<FFI_ArrowArray>,
+    array: &FFI_ArrowArray,
+    schema: &FFI_ArrowSchema,
+) -> Result<ArrowArrayChild<'static>> {
+    if array.dictionary.is_null() || schema.dictionary.is_null() {
+        return Err(ArrowError::CDataInterface(format!(
+            "The dictionary-encoded array \"{}\" has no dictionary",
+            schema.name()
+        )));
+    }
+    unsafe {
+        let arr_ptr = &*array.dictionary;
+        let schema_ptr = &*schema.dictionary;
+        if !arr_ptr.dictionary.is_null() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The values of the dictionary of \"{}\" are themselves dictionary-encoded, \
+                which is not allowed",
+                schema.name()
+            )));
+        }
+        Ok(ArrowArrayChild::from_raw(arr_ptr, schema_ptr, owner))
+    }
+}
+
+/// Options to import arrays from the C Data Interface.
+/// See [ArrowArrayRef::to_data_with_options].
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Whether to drop validity buffers whose bits are all set, so that the imported
+    /// array has no null buffer. This saves memory and allows null-aware kernels
+    /// to take their fast path, at the cost of counting the set bits on import.
+    pub drop_all_valid_null_buffer: bool,
+    /// Whether to error on flags of the schema that are not known to this implementation,
+    /// e.g. because the producer uses a newer version of the specification. By default,
+    /// unknown flags are ignored.
+    pub strict_flags: bool,
+    /// Whether to import the values of dictionaries exported via the same pointer only once,
+    /// e.g. when the producer shares one dictionary between several columns or list children.
+    /// The resulting [DictionaryArray]s then share their values instead of holding
+    /// duplicates. Dictionaries are compared by address, not by content.
+    pub dedupe_dictionaries: bool,
+}
+
+// the flags of the C data interface known to this implementation
+const ARROW_FLAG_DICTIONARY_ORDERED: i64 = 1;
+const ARROW_FLAG_NULLABLE: i64 = 2;
+const ARROW_FLAG_MAP_KEYS_SORTED: i64 = 4;
+const KNOWN_FLAGS: i64 =
+    ARROW_FLAG_DICTIONARY_ORDERED | ARROW_FLAG_NULLABLE | ARROW_FLAG_MAP_KEYS_SORTED;
+
+/// An array imported via [ArrowArrayRef::to_data_owned_mutable]: its buffers are copies
+/// owned by this struct, and can thus be modified in place.
+#[derive(Debug)]
+pub struct OwnedArrayData {
+    /// the data type of the array
+    pub data_type: DataType,
+    /// the length of the array
+    pub len: usize,
+    /// the offset of the array in its buffers
+    pub offset: usize,
+    /// the null count of the array
+    pub null_count: usize,
+    /// the validity buffer, if any
+    pub null_bit_buffer: Option<MutableBuffer>,
+    /// the buffers, as organized by Rust (i.e. without the validity buffer)
+    pub buffers: Vec<MutableBuffer>,
+    /// the children of the array
+    pub child_data: Vec<OwnedArrayData>,
+}
+
+impl OwnedArrayData {
+    /// converts this into an [ArrayData], without copying the buffers
+    pub fn into_data(self) -> ArrayData {
+        ArrayData::new(
+            self.data_type,
+            self.len,
+            Some(self.null_count),
+            self.null_bit_buffer.map(|buffer| buffer.into()),
+            self.offset,
+            self.buffers
+                .into_iter()
+                .map(|buffer| buffer.into())
+                .collect(),
+            self.child_data
+                .into_iter()
+                .map(|child| child.into_data())
+                .collect(),
+        )
+    }
+}
+
+// copies `buffer` into a new, mutable buffer
+fn copy_to_mutable(buffer: &Buffer) -> MutableBuffer {
+    let mut copy = MutableBuffer::new(buffer.len());
+    copy.extend_from_slice(buffer.as_slice());
+    copy
+}
+
+// returns `buffer` if it is aligned to `alignment`, or else a copy of it that is
+fn align_buffer(buffer: &Buffer, alignment: usize) -> Buffer {
+    if buffer.as_ptr().align_offset(alignment) == 0 {
+        return buffer.clone();
+    }
+    // over-allocate so that the copy can start at the first aligned address
+    let mut copy = MutableBuffer::new(buffer.len() + alignment);
+    let padding = copy.as_mut_ptr().align_offset(alignment);
+    copy.extend_zeros(padding);
+    copy.extend_from_slice(buffer.as_slice());
+    let copy: Buffer = copy.into();
+    copy.slice(padding)
+}
+
+// returns `data` with all its buffers and those of its children aligned to `alignment`
+fn align_data(data: &ArrayData, alignment: usize) -> ArrayData {
+    ArrayData::new(
+        data.data_type().clone(),
+        data.len(),
+        Some(data.null_count()),
+        data.null_buffer()
+            .map(|buffer| align_buffer(buffer, alignment)),
+        data.offset(),
+        data.buffers()
+            .iter()
+            .map(|buffer| align_buffer(buffer, alignment))
+            .collect(),
+        data.child_data()
+            .iter()
+            .map(|child| align_data(child, alignment))
+            .collect(),
+    )
+}
+
+// the number of set bits in `[offset, offset + len)` of `bitmap` read most-significant-bit first
+fn count_set_bits_msb_first(bitmap: &[u8], offset: usize, len: usize) -> usize {
+    (offset..offset + len)
+        .filter(|i| bitmap[i / 8] & (0x80 >> (i % 8)) != 0)
+        .count()
+}
+
+/// whether the validity `buffer` has all its bits in `[offset, offset + len)` set
+fn is_all_valid(buffer: &Buffer, offset: usize, len: usize) -> bool {
+    buffer.len() * 8 >= offset + len && buffer.count_set_bits_offset(offset, len) == len
+}
+
+// imports `array` and its children. `dictionaries` holds the values of the dictionaries
+// imported so far by their address, see [ImportOptions::dedupe_dictionaries].
+fn import_data<A: ArrowArrayRef + ?Sized>(
+    array: &A,
+    options: &ImportOptions,
+    dictionaries: &mut HashMap<*const FFI_ArrowArray, ArrayData>,
+) -> Result<ArrayData> {
+    if options.strict_flags && array.schema().flags & !KNOWN_FLAGS != 0 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The schema \"{}\" has unknown flags ({:#b})",
+            array.schema().name(),
+            array.schema().flags & !KNOWN_FLAGS
+        )));
+    }
+    let data_type = array.data_type()?;
+    validate_n_buffers(&data_type, array.array().n_buffers)?;
+    // a common producer mistake is to export the values of a dictionary as a child
+    if let (DataType::Dictionary(_, _), true) =
+        (&data_type, array.array().n_children != 0)
+    {
+        return Err(ArrowError::CDataInterface(format!(
+            "The dictionary-encoded array \"{}\" has {} children, but it must have none: \
+            its values must be exported via `dictionary`",
+            array.schema().name(),
+            array.array().n_children
+        )));
+    }
+    let len = array.array().len();
+    let offset = array.array().offset();
+    let mut null_count = array.array().null_count();
+    let buffers = array.buffers()?;
+    let mut null_bit_buffer = array.null_bit_buffer();
+
+    if options.drop_all_valid_null_buffer {
+        if let Some(buffer) = &null_bit_buffer {
+            if is_all_valid(buffer, offset, len) {
+                null_bit_buffer = None;
+                null_count = 0;
+            }
+        }
+    }
+
+    let mut child_data = (0..array.array().n_children as usize)
+        .map(|i| {
+            let child = array.child(i);
+            import_data(&child, options, dictionaries)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // the values of a dictionary are its first child in Rust
+    if let DataType::Dictionary(_, value_type) = &data_type {
+        let key = array.array().dictionary as *const FFI_ArrowArray;
+        let values = match dictionaries.get(&key) {
+            Some(values)
+                if options.dedupe_dictionaries
+                    && values.data_type() == value_type.as_ref() =>
+            {
+                values.clone()
+            }
+            _ => {
+                let values = import_data(&array.dictionary()?, options, dictionaries)?;
+                if options.dedupe_dictionaries {
+                    dictionaries.insert(key, values.clone());
+                }
+                values
+            }
+        };
+        child_data.push(values);
+    }
+
+    Ok(ArrayData::new(
+        data_type,
+        len,
+        Some(null_count),
+        null_bit_buffer,
+        offset,
+        buffers,
+        child_data,
+    ))
+}
+
+/// A buffer of an array as seen by the importer, as returned by
+/// [ArrowArrayRef::buffer_report].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferInfo {
+    /// the position of the buffer, indexed according to the C data interface
+    pub index: usize,
+    /// the address of the buffer in the producer's memory
+    pub ptr: *const u8,
+    /// the length, in bytes, that the importer reads from `ptr`
+    pub len: usize,
+    /// the largest power of two that `ptr` is a multiple of (0 when `ptr` is null)
+    pub alignment: usize,
+    /// whether `ptr` is null, e.g. for an array without validity buffer
+    pub is_null: bool,
+}
+
 pub trait ArrowArrayRef {
     fn to_data(&self) -> Result<ArrayData> {
-        let data_type = self.data_type()?;
-        let len = self.array().len();
-        let offset = self.array().offset();
-        let null_count = self.array().null_count();
-        let buffers = self.buffers()?;
-        let null_bit_buffer = self.null_bit_buffer();
+        self.to_data_with_options(&ImportOptions::default())
+    }
 
-        let child_data = (0..self.array().n_children as usize)
-            .map(|i| {
-                let child = self.child(i);
-                child.to_data()
-            })
-            .map(|d| d.unwrap())
-            .collect();
+    /// Same as [ArrowArrayRef::to_data], but allows to configure the import via `options`.
+    /// The options are applied to this array and all its children.
+    fn to_data_with_options(&self, options: &ImportOptions) -> Result<ArrayData> {
+        import_data(self, options, &mut HashMap::new())
+    }
+
+    /// Same as [ArrowArrayRef::to_data], but also returns the name of the extension type of
+    /// this array, if any. The array is imported as its storage type, so that extension types
+    /// unknown to this crate are imported rather than rejected, leaving their interpretation
+    /// to the caller.
+    fn to_data_with_extension(&self) -> Result<(ArrayData, Option<String>)> {
+        let extension_name = self.schema().extension_name()?;
+        Ok((self.to_data()?, extension_name))
+    }
+
+    /// Same as [ArrowArrayRef::to_data], but copies every buffer of this array and its
+    /// children into a new [MutableBuffer]. The result does not share any memory with the
+    /// producer, which can thus be released, and can be modified in place.
+    ///
+    /// This copies all buffers, which costs as much as the size of the array.
+    fn to_data_owned_mutable(&self) -> Result<OwnedArrayData> {
+        let mut child_data = (0..self.array().n_children as usize)
+            .map(|i| self.child(i).to_data_owned_mutable())
+            .collect::<Result<Vec<_>>>()?;
+        if let DataType::Dictionary(_, _) = self.data_type()? {
+            child_data.push(self.dictionary()?.to_data_owned_mutable()?);
+        }
+
+        Ok(OwnedArrayData {
+            data_type: self.data_type()?,
+            len: self.array().len(),
+            offset: self.array().offset(),
+            null_count: self.array().null_count(),
+            null_bit_buffer: self.null_bit_buffer().as_ref().map(copy_to_mutable),
+            buffers: self.buffers()?.iter().map(copy_to_mutable).collect(),
+            child_data,
+        })
+    }
+
+    /// Same as [ArrowArrayRef::to_data] for a struct array, but only imports the children at
+    /// `indices`, in that order. The other children are not imported at all, which makes
+    /// projecting a few columns of a wide struct cheap.
+    /// # Error
+    /// Errors if the array is not a struct or if any of the indices is out of bounds.
+    fn to_data_projected(&self, indices: &[usize]) -> Result<ArrayData> {
+        let format = self.schema().try_format()?;
+        if format != "+s" {
+            return Err(ArrowError::CDataInterface(format!(
+                "Only struct arrays can be projected, but the array has format \"{}\"",
+                format
+            )));
+        }
+        let n_children = self.array().n_children as usize;
+        if let Some(index) = indices.iter().find(|index| **index >= n_children) {
+            return Err(ArrowError::CDataInterface(format!(
+                "Cannot project the child {} of a struct array with {} children",
+                index, n_children
+            )));
+        }
+
+        let fields = indices
+            .iter()
+            .map(|index| to_field(self.schema().child(*index)))
+            .collect::<Result<Vec<_>>>()?;
+        let child_data = indices
+            .iter()
+            .map(|index| self.child(*index).to_data())
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(ArrayData::new(
-            data_type,
-            len,
-            Some(null_count),
-            null_bit_buffer,
-            offset,
-            buffers,
+            DataType::Struct(fields),
+            self.array().len(),
+            Some(self.array().null_count()),
+            self.null_bit_buffer(),
+            self.array().offset(),
+            vec![],
             child_data,
         ))
     }
 
+    /// returns the value at `index` of this primitive array, or `None` if it is null, read
+    /// directly from the buffers of the producer. This is a fast path to peek at a few values,
+    /// e.g. a partition key, without importing the whole array.
+    /// # Error
+    /// Errors if the array is not of type `T::DATA_TYPE` or if `index` is out of bounds.
+    fn primitive_value<T: ArrowPrimitiveType>(
+        &self,
+        index: usize,
+    ) -> Result<Option<T::Native>> {
+        let (validity, values) = unsafe {
+            primitive_slices::<T::Native>(self.array(), self.schema(), &T::DATA_TYPE)
+        }?;
+        if index >= values.len() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The index {} is out of bounds of an array of length {}",
+                index,
+                values.len()
+            )));
+        }
+        match validity {
+            Some(validity)
+                if !bit_util::get_bit(validity, self.array().offset() + index) =>
+            {
+                Ok(None)
+            }
+            _ => Ok(Some(values[index])),
+        }
+    }
+
+    /// Same as [ArrowArrayRef::to_data], but first runs [ArrowArrayRef::check_validity]
+    /// on this array and its children to catch gross layout errors of the producer.
+    fn to_data_validated(&self) -> Result<ArrayData> {
+        self.check_validity()?;
+        self.to_data()
+    }
+
+    /// Checks that buffer 0 looks like a validity bitmap: it must be present when
+    /// `null_count > 0`, and its number of unset bits in `[offset, offset + length)`
+    /// must equal `null_count`.
+    ///
+    /// This is a best-effort heuristic: there is no way to know where a producer
+    /// placed its buffers, but a data buffer at position 0 (e.g. buffers swapped by a
+    /// buggy producer) will rarely have the expected number of set bits. Likewise, when the
+    /// count only matches when reading the bits most-significant-bit first, the error
+    /// reports a possible bit-order mismatch (the specification mandates
+    /// least-significant-bit first).
+    ///
+    /// For boolean arrays, whose validity and values are both bitmaps, it also checks that
+    /// the two buffers are not the same memory, which would make every value equal to its
+    /// validity.
+    fn check_validity(&self) -> Result<()> {
+        let array = self.array();
+        if array.n_buffers == 2
+            && !array.buffers.is_null()
+            && self.schema().try_format()? == "b"
+        {
+            let buffers = array.buffers as *const *const u8;
+            // safe because `buffers` has `n_buffers` entries
+            let (validity, values) = unsafe { (*buffers, *buffers.add(1)) };
+            if !validity.is_null() && validity == values {
+                return Err(ArrowError::CDataInterface(
+                    "The validity and the values buffers of the boolean array alias the same \
+                    memory"
+                        .to_string(),
+                ));
+            }
+        }
+        // e.g. the null type has no buffers, and a null count of -1 means "unknown"
+        if array.n_buffers > 0 && array.null_count >= 0 {
+            let length = array.len();
+            let offset = array.offset();
+            let null_count = array.null_count();
+            let buffer_len = bit_util::ceil(offset + length, 8);
+            match unsafe { create_buffer(self.owner().clone(), array, 0, buffer_len) } {
+                None if null_count > 0 => {
+                    return Err(ArrowError::CDataInterface(format!(
+                        "The array has {} nulls, but its validity buffer is null",
+                        null_count
+                    )))
+                }
+                Some(buffer) => {
+                    let set_bits = buffer.count_set_bits_offset(offset, length);
+                    if set_bits + null_count != length {
+                        let msb_set_bits =
+                            count_set_bits_msb_first(buffer.as_slice(), offset, length);
+                        let hint = if msb_set_bits + null_count == length {
+                            "This matches the validity buffer read most-significant-bit \
+                            first: possible bit-order mismatch, as bitmaps must be read \
+                            least-significant-bit first."
+                        } else {
+                            "Are the buffers in the order of the C Data Interface?"
+                        };
+                        return Err(ArrowError::CDataInterface(format!(
+                            "The validity buffer has {} nulls, but the array declares {} nulls. {}",
+                            length - set_bits,
+                            null_count,
+                            hint
+                        )));
+                    }
+                }
+                None => (),
+            }
+        }
+        (0..array.n_children as usize).try_for_each(|i| self.child(i).check_validity())
+    }
+
     /// returns all buffers, as organized by Rust (i.e. null buffer is skipped)
     fn buffers(&self) -> Result<Vec<Buffer>> {
-        (0..self.array().n_buffers - 1)
+        let data_type = self.data_type()?;
+        // `max` guards against arrays without buffers, such as the null type
+        (0..(self.array().n_buffers - 1).max(0))
             .map(|index| {
                 // + 1: skip null buffer
                 let index = (index + 1) as usize;
 
                 let len = self.buffer_len(index)?;
+                check_values_alignment(self.array(), &data_type, index)?;
 
                 unsafe { create_buffer(self.owner().clone(), self.array(), index, len) }
                     .ok_or_else(|| {
@@ -633,41 +2115,31 @@ pub trait ArrowArrayRef {
             | (DataType::LargeBinary, 1)
             | (DataType::List(_), 1)
             | (DataType::LargeList(_), 1) => {
-                // the len of the offset buffer (buffer 1) equals length + 1
+                // the len of the offset buffer (buffer 1) equals offset + length + 1
                 let bits = bit_width(data_type, i)?;
                 debug_assert_eq!(bits % 8, 0);
-                (self.array().length as usize + 1) * (bits / 8)
+                (self.array().offset() + self.array().len() + 1) * (bits / 8)
             }
             (DataType::Utf8, 2) | (DataType::Binary, 2) | (DataType::List(_), 2) => {
                 // the len of the data buffer (buffer 2) equals the last value of the offset buffer (buffer 1)
-                let len = self.buffer_len(1)?;
-                // first buffer is the null buffer => add(1)
-                // we assume that pointer is aligned for `i32`, as Utf8 uses `i32` offsets.
-                #[allow(clippy::cast_ptr_alignment)]
-                let offset_buffer = unsafe {
-                    *(self.array().buffers as *mut *const u8).add(1) as *const i32
-                };
-                // get last offset
-                (unsafe { *offset_buffer.add(len / size_of::<i32>() - 1) }) as usize
+                // Utf8 uses `i32` offsets, whose alignment is checked by `last_offset`.
+                unsafe { last_offset::<i32>(self.array()) }?
             }
             (DataType::LargeUtf8, 2)
             | (DataType::LargeBinary, 2)
             | (DataType::LargeList(_), 2) => {
                 // the len of the data buffer (buffer 2) equals the last value of the offset buffer (buffer 1)
-                let len = self.buffer_len(1)?;
-                // first buffer is the null buffer => add(1)
-                // we assume that pointer is aligned for `i64`, as Large uses `i64` offsets.
-                #[allow(clippy::cast_ptr_alignment)]
-                let offset_buffer = unsafe {
-                    *(self.array().buffers as *mut *const u8).add(1) as *const i64
-                };
-                // get last offset
-                (unsafe { *offset_buffer.add(len / size_of::<i64>() - 1) }) as usize
+                // Large uses `i64` offsets, whose alignment is checked by `last_offset`.
+                unsafe { last_offset::<i64>(self.array()) }?
+            }
+            (DataType::Boolean, 1) => {
+                // booleans are bit-packed: the offset is in bits and is part of the buffer
+                bit_util::ceil(self.array().offset() + self.array().len(), 8)
             }
-            // buffer len of primitive types
+            // buffer len of primitive types: the values before the offset are part of the buffer
             _ => {
                 let bits = bit_width(data_type, i)?;
-                bit_util::ceil(self.array().length as usize * bits, 8)
+                bit_util::ceil((self.array().offset() + self.array().len()) * bits, 8)
             }
         })
     }
@@ -676,16 +2148,106 @@ pub trait ArrowArrayRef {
     /// Rust implementation uses a buffer that is not part of the array of buffers.
     /// The C Data interface's null buffer is part of the array of buffers.
     fn null_bit_buffer(&self) -> Option<Buffer> {
+        // e.g. the null type has no buffers, not even a validity buffer
+        if self.array().n_buffers == 0 {
+            return None;
+        }
         // similar to `self.buffer_len(0)`, but without `Result`.
-        let buffer_len = bit_util::ceil(self.array().length as usize, 8);
+        // the offset is in bits and is part of the buffer
+        let buffer_len = bit_util::ceil(self.array().offset() + self.array().len(), 8);
 
         unsafe { create_buffer(self.owner().clone(), self.array(), 0, buffer_len) }
     }
 
+    /// Returns the number of bytes that importing this array reads from the producer's
+    /// memory, i.e. the sum of the lengths of the buffers of this array and its children.
+    /// No [Buffer] is created, so that imports can be audited before they run, e.g. to
+    /// reject arrays larger than a threshold.
+    fn import_read_bytes(&self) -> Result<usize> {
+        let array = self.array();
+        let mut bytes = 0;
+        // the validity buffer is optional
+        if array.n_buffers > 0 && !unsafe { *array.buffers }.is_null() {
+            bytes += bit_util::ceil(array.offset() + array.len(), 8);
+        }
+        for i in 1..array.n_buffers as usize {
+            bytes += self.buffer_len(i)?;
+        }
+        for i in 0..array.n_children as usize {
+            bytes += self.child(i).import_read_bytes()?;
+        }
+        if !array.dictionary.is_null() {
+            bytes += self.dictionary()?.import_read_bytes()?;
+        }
+        Ok(bytes)
+    }
+
+    /// Returns whether the [Buffer] imported at position `index` (indexed according to the
+    /// C data interface, i.e. the validity buffer is at position 0) starts at `ptr`. Use
+    /// this to verify that an import shares the producer's memory instead of copying it.
+    /// Returns false if the buffer does not exist or is null.
+    fn buffer_shares_with(&self, index: usize, ptr: *const u8) -> bool {
+        if index >= self.array().n_buffers as usize {
+            return false;
+        }
+        let buffer = if index == 0 {
+            self.null_bit_buffer()
+        } else {
+            match self.buffer_len(index) {
+                Ok(len) => unsafe {
+                    create_buffer(self.owner().clone(), self.array(), index, len)
+                },
+                Err(_) => None,
+            }
+        };
+        matches!(buffer, Some(buffer) if buffer.as_ptr() == ptr)
+    }
+
+    /// Returns the pointer, length and alignment of each buffer of this array (but not of
+    /// its children), i.e. exactly what the importer reads. Use this to debug memory issues
+    /// across the C Data Interface. The buffers are backed by [ArrowArrayRef::owner].
+    /// # Error
+    /// Errors if the length of any buffer cannot be computed.
+    fn buffer_report(&self) -> Result<Vec<BufferInfo>> {
+        let array = self.array();
+        (0..array.n_buffers.max(0) as usize)
+            .map(|index| {
+                // safe because `buffers` has `n_buffers` entries
+                let ptr = unsafe { *array.buffers.add(index) } as *const u8;
+                let is_null = ptr.is_null();
+                let len = match (is_null, index) {
+                    (true, _) => 0,
+                    // the offset is in bits and is part of the buffer
+                    (false, 0) => bit_util::ceil(array.offset() + array.len(), 8),
+                    (false, _) => self.buffer_len(index)?,
+                };
+                let alignment = if is_null {
+                    0
+                } else {
+                    1 << (ptr as usize).trailing_zeros()
+                };
+                Ok(BufferInfo {
+                    index,
+                    ptr,
+                    len,
+                    alignment,
+                    is_null,
+                })
+            })
+            .collect()
+    }
+
     fn child(&self, index: usize) -> ArrowArrayChild {
         create_child(self.owner().clone(), self.array(), self.schema(), index)
     }
 
+    /// returns the values of this dictionary-encoded array.
+    /// # Error
+    /// Errors if the array or its schema has no dictionary.
+    fn dictionary(&self) -> Result<ArrowArrayChild> {
+        create_dictionary(self.owner().clone(), self.array(), self.schema())
+    }
+
     fn owner(&self) -> &Arc<FFI_ArrowArray>;
     fn array(&self) -> &FFI_ArrowArray;
     fn schema(&self) -> &FFI_ArrowSchema;
@@ -711,7 +2273,23 @@ pub trait ArrowArrayRef {
 /// calling [FFI_ArrowArray::release] and [FFI_ArrowSchema::release] accordingly.
 ///
 /// Furthermore, this struct assumes that the incoming data agrees with the C data interface.
-#[derive(Debug)]
+///
+/// # Panics and unwinding
+/// Dropping an imported [ArrowArray] releases it, including while unwinding from a panic.
+/// The buffers and children imported from it share its ownership, so that the producer's
+/// release callback is called exactly once, when the last of them is dropped. This also
+/// holds when an import fails or panics midway: whatever was already imported is dropped
+/// and the array is released once.
+///
+/// # Clone
+/// Cloning an [ArrowArray] is cheap: the clones share the same [FFI_ArrowArray] and
+/// [FFI_ArrowSchema], which are released once the last clone, and everything imported from
+/// it, is dropped. Consequently, [ArrowArray::into_raw] of two clones returns the same
+/// pointers, which must not be handed to two consumers, as each of them would release it.
+/// To export an array to several consumers, e.g. to broadcast it to plugins, re-export each
+/// clone via `ArrowArray::try_from(ArrayData::try_from(clone)?)`: each consumer gets its own
+/// structs, while the buffers are shared.
+#[derive(Clone, Debug)]
 pub struct ArrowArray {
     array: Arc<FFI_ArrowArray>,
     schema: Arc<FFI_ArrowSchema>,
@@ -764,22 +2342,221 @@ impl<'a> ArrowArrayRef for ArrowArrayChild<'a> {
 
 impl ArrowArray {
     /// creates a new `ArrowArray`. This is used to export to the C Data Interface.
+    ///
+    /// [ArrayData] does not declare whether it is nullable, so the exported schema is
+    /// nullable, regardless of whether `data` contains nulls. Use
+    /// [ArrowArray::try_new_full] to export a non-nullable field.
     /// # Safety
     /// See safety of [ArrowArray]
     #[allow(clippy::too_many_arguments)]
     pub unsafe fn try_new(data: ArrayData) -> Result<Self> {
-        let field = Field::new("", data.data_type().clone(), data.null_count() != 0);
+        let field = Field::new("", data.data_type().clone(), true);
+        Self::try_new_full(data, field)
+    }
+
+    /// creates a new `ArrowArray` after checking that the offsets of `data` and of its
+    /// children are valid: they must be monotonically increasing and must not exceed the
+    /// length of the values they point to. This allows producers to catch corrupted arrays
+    /// before handing them over to a consumer.
+    /// This is used to export to the C Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    /// # Error
+    /// Errors if any of the offsets is invalid.
+    pub unsafe fn try_new_checked(data: ArrayData) -> Result<Self> {
+        validate_offsets(&data)?;
+        Self::try_new(data)
+    }
+
+    /// creates a new `ArrowArray` whose length is `logical_length`, e.g. for producers whose
+    /// buffers are larger than the number of valid elements. The buffers are exported whole,
+    /// but the consumer only reads the first `logical_length` elements.
+    /// This is used to export to the C Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    /// # Error
+    /// Errors if `logical_length` exceeds the length of `data`, i.e. its buffers' capacity.
+    pub unsafe fn try_new_with_length(
+        data: ArrayData,
+        logical_length: usize,
+    ) -> Result<Self> {
+        if logical_length > data.len() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The logical length ({}) exceeds the length of the array ({})",
+                logical_length,
+                data.len()
+            )));
+        }
+        Self::try_new(data.slice(0, logical_length))
+    }
+
+    /// creates a new `ArrowArray` from an [Array], e.g. the output of a builder.
+    /// This is used to export to the C Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    pub unsafe fn from_array(array: &dyn Array) -> Result<Self> {
+        Self::try_new(array.data().clone())
+    }
+
+    /// creates a new `ArrowArray` whose schema is exported from `field`, thereby preserving
+    /// its name and nullability. This is used to export to the C Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    /// # Error
+    /// Errors if `field`'s data type differs from `data`'s, or if `field` is not nullable
+    /// but `data` contains nulls.
+    pub unsafe fn try_new_full(data: ArrayData, field: Field) -> Result<Self> {
+        if field.data_type() != data.data_type() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The field \"{}\" has data type {:?}, but the array has data type {:?}",
+                field.name(),
+                field.data_type(),
+                data.data_type()
+            )));
+        }
+        if !field.is_nullable() && data.null_count() != 0 {
+            return Err(ArrowError::CDataInterface(format!(
+                "The field \"{}\" is not nullable, but the array contains {} nulls",
+                field.name(),
+                data.null_count()
+            )));
+        }
+
         let array = Arc::new(FFI_ArrowArray::new(&data));
         let schema = Arc::new(FFI_ArrowSchema::try_new(field)?);
 
         Ok(ArrowArray { array, schema })
     }
 
-    /// creates a new [ArrowArray] from two pointers. Used to import from the C Data Interface.
+    /// creates a new `ArrowArray` that references the already exported `schema` instead of
+    /// exporting a new one. Use this to export many arrays with the same schema, e.g.
+    /// the batches of a stream, building the [FFI_ArrowSchema] only once.
+    /// The schema is released once all arrays referencing it are dropped.
+    /// This is used to export to the C Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    /// # Error
+    /// Errors if `schema`'s data type differs from `data`'s.
+    pub unsafe fn try_new_with_schema(
+        data: ArrayData,
+        schema: Arc<FFI_ArrowSchema>,
+    ) -> Result<Self> {
+        let data_type = to_field(&schema)?.data_type().clone();
+        if &data_type != data.data_type() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The schema has data type {:?}, but the array has data type {:?}",
+                data_type,
+                data.data_type()
+            )));
+        }
+
+        let array = Arc::new(FFI_ArrowArray::new(&data));
+
+        Ok(ArrowArray { array, schema })
+    }
+
+    /// creates a new `ArrowArray` whose buffers, including those of its children, start at
+    /// an address that is a multiple of `alignment`, e.g. 4096 for consumers that require
+    /// page-aligned buffers. This is used to export to the C Data Interface.
+    ///
+    /// Buffers that are already aligned are exported without a copy. Every other buffer
+    /// is copied into a new allocation of its length plus `alignment` bytes.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    /// # Error
+    /// Errors if `alignment` is not a power of two.
+    pub unsafe fn try_new_aligned(data: ArrayData, alignment: usize) -> Result<Self> {
+        if !alignment.is_power_of_two() {
+            return Err(ArrowError::CDataInterface(format!(
+                "The alignment must be a power of two, but it is {}",
+                alignment
+            )));
+        }
+        Self::try_new(align_data(&data, alignment))
+    }
+
+    /// creates a new `ArrowArray` that calls `on_release` when the consumer releases it,
+    /// e.g. to track resources that must outlive the exported array.
+    /// This is used to export to the C Data Interface.
+    ///
+    /// `on_release` must not panic: it is called from the release callback, which cannot
+    /// unwind into the consumer, so a panic aborts the process. It is `Send` because the
+    /// consumer may release the array from any thread.
     /// # Safety
     /// See safety of [ArrowArray]
+    pub unsafe fn try_new_with_release_hook(
+        data: ArrayData,
+        on_release: Box<dyn FnOnce() + Send>,
+    ) -> Result<Self> {
+        let mut this = Self::try_new(data)?;
+        // the array was just created and thus has no other owner
+        let array = Arc::get_mut(&mut this.array).unwrap();
+        let private = &mut *(array.private_data as *mut PrivateData);
+        private.on_release = Some(on_release);
+        Ok(this)
+    }
+
+    /// returns the [Field] declared in the schema of this array, e.g. to inspect the name,
+    /// data type and nullability of an imported array.
+    /// # Error
+    /// Errors if the schema's data type is not supported.
+    pub fn field(&self) -> Result<Field> {
+        to_field(&self.schema)
+    }
+
+    /// imports this array as a [GenericListArray], i.e. as a `ListArray` for `i32` offsets
+    /// and as a `LargeListArray` for `i64` offsets.
+    /// # Error
+    /// Errors if the array cannot be imported or is not a list with offsets of type `O`.
+    pub fn into_list<O: OffsetSizeTrait>(self) -> Result<GenericListArray<O>> {
+        let data = self.to_data()?;
+        match (data.data_type(), O::is_large()) {
+            (DataType::List(_), false) | (DataType::LargeList(_), true) => {
+                Ok(GenericListArray::<O>::from(data))
+            }
+            (data_type, is_large) => Err(ArrowError::CDataInterface(format!(
+                "Expected an array of type {}, but the array is of type {:?}",
+                if is_large { "LargeList" } else { "List" },
+                data_type
+            ))),
+        }
+    }
+
+    /// imports this array as a [DictionaryArray] with keys of type `K`. Contrarily to
+    /// `DictionaryArray::from(ArrayData)`, this preserves whether the producer declared
+    /// the dictionary as ordered (`ARROW_FLAG_DICTIONARY_ORDERED`).
+    /// # Error
+    /// Errors if the array cannot be imported or is not a dictionary with keys of type `K`.
+    pub fn into_dictionary<K: ArrowPrimitiveType>(self) -> Result<DictionaryArray<K>> {
+        let is_ordered = self.schema.flags & ARROW_FLAG_DICTIONARY_ORDERED != 0;
+        let data = self.to_data()?;
+        match data.data_type() {
+            DataType::Dictionary(key_type, _) if key_type.as_ref() == &K::DATA_TYPE => {
+                Ok(DictionaryArray::<K>::from(data).with_is_ordered(is_ordered))
+            }
+            data_type => Err(ArrowError::CDataInterface(format!(
+                "Expected a dictionary with keys of type {:?}, but the array is of type {:?}",
+                K::DATA_TYPE,
+                data_type
+            ))),
+        }
+    }
+
+    /// Validates that the exported [FFI_ArrowArray] and [FFI_ArrowSchema] are consistent,
+    /// e.g. that the number of buffers matches the data type and that the children of the
+    /// array match the children of the schema. Use this to check an export before handing
+    /// it over to a consumer.
+    pub fn validate_export(&self) -> Result<()> {
+        validate_exported(&self.array, &self.schema)
+    }
+
+    /// creates a new [ArrowArray] from two pointers. Used to import from the C Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]. In particular, the pointers must not be imported twice:
+    /// each import takes ownership of them. In debug builds, a double import is detected
+    /// and errors.
     /// # Error
-    /// Errors if any of the pointers is null
+    /// Errors if any of the pointers is null, or if the array or the schema is released
     pub unsafe fn try_from_raw(
         array: *const FFI_ArrowArray,
         schema: *const FFI_ArrowSchema,
@@ -790,12 +2567,86 @@ impl ArrowArray {
                     .to_string(),
             ));
         };
+        if (*array).release.is_none() || (*schema).release.is_none() {
+            return Err(ArrowError::CDataInterface(
+                "The array or the schema passed to `try_from_raw` is released"
+                    .to_string(),
+            ));
+        }
+        // importing the same pointers twice would release them twice. This is only
+        // detected in debug builds.
+        if !register_import(array) {
+            return Err(ArrowError::CDataInterface(
+                "The array passed to `try_from_raw` was already imported".to_string(),
+            ));
+        }
         Ok(Self {
             array: Arc::from_raw(array as *mut FFI_ArrowArray),
             schema: Arc::from_raw(schema as *mut FFI_ArrowSchema),
         })
     }
 
+    /// Defers the release of this imported array: once this array and all arrays imported
+    /// from it are dropped, the producer's array is handed to `sink` as a [DeferredRelease]
+    /// instead of being released inline. This allows to marshal the release to the thread
+    /// required by the producer, e.g. by sending it over a channel.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    /// # Error
+    /// Errors if this array was already shared, e.g. because it was already imported.
+    pub unsafe fn defer_release(
+        mut self,
+        sink: Box<dyn FnOnce(DeferredRelease) + Send>,
+    ) -> Result<Self> {
+        let array = Arc::get_mut(&mut self.array).ok_or_else(|| {
+            ArrowError::CDataInterface(
+                "The release of a shared array cannot be deferred".to_string(),
+            )
+        })?;
+        if array.release.is_none() {
+            // already released: there is nothing to defer
+            return Ok(self);
+        }
+        let inner = std::mem::replace(array, FFI_ArrowArray::empty());
+        // `array` is now empty: overwrite it without dropping it, as it is still imported
+        let mut wrapper = inner.with_release(release_deferred);
+        wrapper.private_data =
+            Box::into_raw(Box::new(DeferredPrivateData { array: inner, sink }))
+                as *mut ::std::os::raw::c_void;
+        ptr::write(array, wrapper);
+        Ok(self)
+    }
+
+    /// Ties the memory of this imported array to `owner`: once this array and all arrays
+    /// imported from it are dropped, the producer's array is released, and then `owner` is
+    /// released. This allows to import arrays whose buffers live in memory that this process
+    /// must clean up itself, e.g. a shared memory segment that must be unmapped.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    /// # Error
+    /// Errors if this array was already shared, e.g. because it was already imported.
+    pub unsafe fn with_owner(mut self, owner: Box<dyn ImportOwner>) -> Result<Self> {
+        let array = Arc::get_mut(&mut self.array).ok_or_else(|| {
+            ArrowError::CDataInterface(
+                "The owner of a shared array cannot be changed".to_string(),
+            )
+        })?;
+        if array.release.is_none() {
+            // already released: the memory is no longer used
+            owner.release();
+            return Ok(self);
+        }
+        let inner = std::mem::replace(array, FFI_ArrowArray::empty());
+        // `array` is now empty: overwrite it without dropping it, as it is still imported
+        let mut wrapper = inner.with_release(release_owned);
+        wrapper.private_data = Box::into_raw(Box::new(OwnedPrivateData {
+            array: inner,
+            owner,
+        })) as *mut ::std::os::raw::c_void;
+        ptr::write(array, wrapper);
+        Ok(self)
+    }
+
     /// creates a new empty [ArrowArray]. Used to import from the C Data Interface.
     /// # Safety
     /// See safety of [ArrowArray]
@@ -807,35 +2658,789 @@ impl ArrowArray {
 
     /// exports [ArrowArray] to the C Data Interface
     pub fn into_raw(this: ArrowArray) -> (*const FFI_ArrowArray, *const FFI_ArrowSchema) {
+        // the ownership is transferred: the pointers can be imported again
+        unregister_import(Arc::as_ptr(&this.array));
         (Arc::into_raw(this.array), Arc::into_raw(this.schema))
     }
 }
 
-impl<'a> ArrowArrayChild<'a> {
-    fn from_raw(
-        array: &'a FFI_ArrowArray,
-        schema: &'a FFI_ArrowSchema,
-        owner: Arc<FFI_ArrowArray>,
-    ) -> Self {
-        Self {
-            array,
-            schema,
-            owner,
-        }
+/// Imports a record batch, as [RecordBatch::from_raw], after checking that its schema is
+/// compatible with `expected`: its columns must have the names, data types and nullability
+/// of the fields of `expected`. This guards against a producer whose schema drifted.
+/// # Safety
+/// Assumes that these pointers represent valid C Data Interfaces, both in memory
+/// representation and lifetime via the `release` mechanism.
+/// # Error
+/// Errors with the first column that does not match `expected`, in which case the array and
+/// the schema are not imported.
+pub unsafe fn import_expecting(
+    array: *const FFI_ArrowArray,
+    schema: *const FFI_ArrowSchema,
+    expected: &Schema,
+) -> Result<RecordBatch> {
+    if schema.is_null() {
+        return Err(ArrowError::MemoryError(
+            "The schema passed to `import_expecting` is null".to_string(),
+        ));
+    }
+    let imported = Schema::try_from(&*schema)?;
+    if imported.fields().len() != expected.fields().len() {
+        return Err(ArrowError::CDataInterface(format!(
+            "The batch has {} columns, but {} were expected",
+            imported.fields().len(),
+            expected.fields().len()
+        )));
+    }
+    let mismatch = imported
+        .fields()
+        .iter()
+        .zip(expected.fields().iter())
+        .enumerate()
+        .find(|(_, (field, expected))| {
+            field.name() != expected.name()
+                || field.data_type() != expected.data_type()
+                || field.is_nullable() != expected.is_nullable()
+        });
+    if let Some((i, (field, expected))) = mismatch {
+        return Err(ArrowError::CDataInterface(format!(
+            "The column {} is \"{}\" of type {:?} (nullable: {}), but \"{}\" of type {:?} \
+            (nullable: {}) was expected",
+            i,
+            field.name(),
+            field.data_type(),
+            field.is_nullable(),
+            expected.name(),
+            expected.data_type(),
+            expected.is_nullable()
+        )));
     }
+    RecordBatch::from_raw(array, schema)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::array::{
-        make_array, Array, ArrayData, BinaryOffsetSizeTrait, BooleanArray,
-        GenericBinaryArray, GenericListArray, GenericStringArray, Int32Array,
-        OffsetSizeTrait, StringOffsetSizeTrait, Time32MillisecondArray,
+/// Exports `data` to the C Data Interface into structs allocated by the consumer, as
+/// producers usually do, e.g. to implement `__arrow_c_array__`. The consumer owns the
+/// exported structs, and must release them once it is done with them.
+/// # Safety
+/// `out_array` and `out_schema` must be valid for writes. Their previous content, if any, is
+/// overwritten without being released.
+/// # Error
+/// Errors if the data type of `data` is not supported, in which case nothing is written.
+pub unsafe fn export_into_raw(
+    data: ArrayData,
+    out_array: *mut FFI_ArrowArray,
+    out_schema: *mut FFI_ArrowSchema,
+) -> Result<()> {
+    // the schema is exported first, as it is the fallible part
+    let field = Field::new("", data.data_type().clone(), true);
+    let schema = FFI_ArrowSchema::try_new(field)?;
+    let array = FFI_ArrowArray::new(&data);
+
+    ptr::write(out_schema, schema);
+    ptr::write(out_array, array);
+    Ok(())
+}
+
+/// Exports `data` to the C Data Interface and imports it back, as an external consumer
+/// would. This allows to verify that arrays survive the C Data Interface without a consumer.
+#[cfg(any(test, feature = "testing"))]
+pub fn roundtrip(data: ArrayData) -> Result<ArrayData> {
+    let array = unsafe { ArrowArray::try_new(data) }?;
+    let (array, schema) = ArrowArray::into_raw(array);
+    let array = unsafe { ArrowArray::try_from_raw(array, schema) }?;
+    array.to_data()
+}
+
+/// The device type of arrays in the CPU's memory, see [FFI_ArrowDeviceArray].
+pub const ARROW_DEVICE_CPU: i32 = 1;
+
+/// ABI-compatible struct for `ArrowDeviceArray` from the C Device Data Interface.
+/// It wraps an [FFI_ArrowArray] with the device on which its buffers are allocated.
+/// See <https://arrow.apache.org/docs/format/CDeviceDataInterface.html>
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFI_ArrowDeviceArray {
+    array: FFI_ArrowArray,
+    device_id: i64,
+    device_type: i32,
+    sync_event: *mut ::std::os::raw::c_void,
+    reserved: [i64; 3],
+}
+
+impl FFI_ArrowDeviceArray {
+    // create an empty `FFI_ArrowDeviceArray`, which can be used to import data into
+    fn empty() -> Self {
+        Self {
+            array: FFI_ArrowArray::empty(),
+            device_id: 0,
+            device_type: 0,
+            sync_event: std::ptr::null_mut(),
+            reserved: [0; 3],
+        }
+    }
+
+    /// the type of the device on which the buffers are allocated, e.g. [ARROW_DEVICE_CPU]
+    pub fn device_type(&self) -> i32 {
+        self.device_type
+    }
+
+    /// the id of the device on which the buffers are allocated
+    pub fn device_id(&self) -> i64 {
+        self.device_id
+    }
+}
+
+/// Struct used to move an Array from and to the C Device Data Interface.
+/// This is the counterpart of [ArrowArray] for [FFI_ArrowDeviceArray]: the schema is
+/// an [FFI_ArrowSchema], as in the C Data Interface.
+///
+/// Only arrays on the CPU are supported: they are exported with the device type
+/// [ARROW_DEVICE_CPU] and can be imported via [ArrowDeviceArray::into_array].
+///
+/// # Safety
+/// See safety of [ArrowArray]
+#[derive(Debug)]
+pub struct ArrowDeviceArray {
+    array: Arc<FFI_ArrowDeviceArray>,
+    schema: Arc<FFI_ArrowSchema>,
+}
+
+impl ArrowDeviceArray {
+    /// creates a new `ArrowDeviceArray` of an array on the CPU.
+    /// This is used to export to the C Device Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    pub unsafe fn try_new(data: ArrayData) -> Result<Self> {
+        let field = Field::new("", data.data_type().clone(), true);
+        let array = Arc::new(FFI_ArrowDeviceArray {
+            array: FFI_ArrowArray::new(&data),
+            device_id: 0,
+            device_type: ARROW_DEVICE_CPU,
+            sync_event: std::ptr::null_mut(),
+            reserved: [0; 3],
+        });
+        let schema = Arc::new(FFI_ArrowSchema::try_new(field)?);
+        Ok(Self { array, schema })
+    }
+
+    /// creates a new [ArrowDeviceArray] from two pointers.
+    /// Used to import from the C Device Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray::try_from_raw]
+    /// # Error
+    /// Errors if any of the pointers is null, or if the array or the schema is released
+    pub unsafe fn try_from_raw(
+        array: *const FFI_ArrowDeviceArray,
+        schema: *const FFI_ArrowSchema,
+    ) -> Result<Self> {
+        if array.is_null() || schema.is_null() {
+            return Err(ArrowError::MemoryError(
+                "At least one of the pointers passed to `try_from_raw` is null"
+                    .to_string(),
+            ));
+        };
+        if (*array).array.release.is_none() || (*schema).release.is_none() {
+            return Err(ArrowError::CDataInterface(
+                "The array or the schema passed to `try_from_raw` is released"
+                    .to_string(),
+            ));
+        }
+        // as for [ArrowArray::try_from_raw], the wrapped array is registered: it is
+        // unregistered when it is dropped.
+        if !register_import(&(*array).array) {
+            return Err(ArrowError::CDataInterface(
+                "The array passed to `try_from_raw` was already imported".to_string(),
+            ));
+        }
+        Ok(Self {
+            array: Arc::from_raw(array as *mut FFI_ArrowDeviceArray),
+            schema: Arc::from_raw(schema as *mut FFI_ArrowSchema),
+        })
+    }
+
+    /// creates a new empty [ArrowDeviceArray]. Used to import from the C Device Data Interface.
+    /// # Safety
+    /// See safety of [ArrowArray]
+    pub unsafe fn empty() -> Self {
+        let schema = Arc::new(FFI_ArrowSchema::empty());
+        let array = Arc::new(FFI_ArrowDeviceArray::empty());
+        Self { array, schema }
+    }
+
+    /// exports [ArrowDeviceArray] to the C Device Data Interface
+    pub fn into_raw(
+        this: ArrowDeviceArray,
+    ) -> (*const FFI_ArrowDeviceArray, *const FFI_ArrowSchema) {
+        // the ownership is transferred: the pointers can be imported again
+        unregister_import(&this.array.array);
+        (Arc::into_raw(this.array), Arc::into_raw(this.schema))
+    }
+
+    /// the type of the device on which the buffers are allocated, e.g. [ARROW_DEVICE_CPU]
+    pub fn device_type(&self) -> i32 {
+        self.array.device_type
+    }
+
+    /// the id of the device on which the buffers are allocated
+    pub fn device_id(&self) -> i64 {
+        self.array.device_id
+    }
+
+    /// converts this into an [ArrowArray], from which the array can be imported.
+    /// # Error
+    /// Errors if the array is not on the CPU, or if this array is shared.
+    pub fn into_array(self) -> Result<ArrowArray> {
+        if self.array.device_type != ARROW_DEVICE_CPU {
+            return Err(ArrowError::CDataInterface(format!(
+                "The array is on a device of type {} (id {}), but only arrays on the CPU \
+                (type {}) can be imported",
+                self.array.device_type, self.array.device_id, ARROW_DEVICE_CPU
+            )));
+        }
+        let address: *const FFI_ArrowArray = &self.array.array;
+        let device_array = Arc::try_unwrap(self.array).map_err(|_| {
+            ArrowError::CDataInterface(
+                "A shared device array cannot be imported".to_string(),
+            )
+        })?;
+        // the wrapped array is moved out of the imported pointer
+        unregister_import(address);
+        Ok(ArrowArray {
+            array: Arc::new(device_array.array),
+            schema: self.schema,
+        })
+    }
+}
+
+// checks that the offsets of `data` and its children (if any) are valid
+fn validate_offsets(data: &ArrayData) -> Result<()> {
+    match data.data_type() {
+        DataType::Utf8 | DataType::Binary => {
+            validate_offsets_of::<i32>(data, data.buffers()[1].len())?
+        }
+        DataType::LargeUtf8 | DataType::LargeBinary => {
+            validate_offsets_of::<i64>(data, data.buffers()[1].len())?
+        }
+        DataType::List(_) => {
+            validate_offsets_of::<i32>(data, data.child_data()[0].len())?
+        }
+        DataType::LargeList(_) => {
+            validate_offsets_of::<i64>(data, data.child_data()[0].len())?
+        }
+        _ => (),
+    };
+    data.child_data().iter().try_for_each(validate_offsets)
+}
+
+// checks that the offsets (buffer 0) of `data` are monotonically increasing, and within
+// `[0, values_len]`
+fn validate_offsets_of<O: OffsetSizeTrait>(
+    data: &ArrayData,
+    values_len: usize,
+) -> Result<()> {
+    let buffer = &data.buffers()[0];
+    if buffer.is_empty() && data.is_empty() {
+        return Ok(());
+    }
+    // `typed_data` panics on misaligned buffers
+    if (buffer.as_ptr() as usize) % std::mem::align_of::<O>() != 0 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The offset buffer at {:p} is not aligned to the {} bytes of its offsets",
+            buffer.as_ptr(),
+            std::mem::align_of::<O>()
+        )));
+    }
+    let offsets = unsafe { buffer.typed_data::<O>() };
+    let offsets = offsets
+        .get(data.offset()..=data.offset() + data.len())
+        .ok_or_else(|| {
+            ArrowError::CDataInterface(format!(
+                "The array of length {} and offset {} has only {} offsets",
+                data.len(),
+                data.offset(),
+                offsets.len()
+            ))
+        })?;
+
+    if let Some(i) = offsets.windows(2).position(|w| w[0] > w[1]) {
+        return Err(ArrowError::CDataInterface(format!(
+            "The offsets of the array are not monotonically increasing at slot {}",
+            i
+        )));
+    }
+    if offsets[0].to_usize().is_none() {
+        return Err(ArrowError::CDataInterface(
+            "The first offset of the array is negative".to_string(),
+        ));
+    }
+    match offsets[offsets.len() - 1].to_usize() {
+        Some(last) if last <= values_len => Ok(()),
+        _ => Err(ArrowError::CDataInterface(format!(
+            "The last offset of the array ({:?}) exceeds the length of its values ({})",
+            offsets[offsets.len() - 1],
+            values_len
+        ))),
+    }
+}
+
+/// Returns an [ArrayData] equal to `data` whose buffers are all aligned to [ALIGNMENT].
+///
+/// Buffers imported from the C Data Interface have whatever alignment the producer chose,
+/// while some kernels in this crate assume that buffers are aligned. Buffers that are
+/// already aligned are shared; the others are copied into new, Rust-owned buffers.
+pub fn realign(data: &ArrayData) -> ArrayData {
+    ArrayData::new(
+        data.data_type().clone(),
+        data.len(),
+        Some(data.null_count()),
+        data.null_buffer().map(realign_buffer),
+        data.offset(),
+        data.buffers().iter().map(realign_buffer).collect(),
+        data.child_data().iter().map(realign).collect(),
+    )
+}
+
+fn realign_buffer(buffer: &Buffer) -> Buffer {
+    if buffer.as_ptr() as usize % ALIGNMENT == 0 {
+        buffer.clone()
+    } else {
+        Buffer::from(buffer.as_slice())
+    }
+}
+
+/// Exports `indices` and `values` as a dictionary-encoded array, without building a
+/// [DictionaryArray]: the format of the exported array is the format of the indices, and
+/// `values` are exported via its `dictionary`. `ordered` declares whether the values are
+/// ordered (`ARROW_FLAG_DICTIONARY_ORDERED`).
+///
+/// The buffers of `indices` and `values` are shared, not copied.
+/// # Error
+/// Errors if `indices` are not integers, or if `values` cannot be exported.
+pub fn export_dictionary(
+    indices: ArrayData,
+    values: ArrayData,
+    ordered: bool,
+) -> Result<ArrowArray> {
+    let key_type = indices.data_type().clone();
+    if !matches!(
+        key_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+    ) {
+        return Err(ArrowError::CDataInterface(format!(
+            "The indices of a dictionary must be integers, but they are {:?}",
+            key_type
+        )));
+    }
+    let data_type =
+        DataType::Dictionary(Box::new(key_type), Box::new(values.data_type().clone()));
+    let field = Field::new_dict("", data_type.clone(), true, 0, ordered);
+    let data = ArrayData::new(
+        data_type,
+        indices.len(),
+        Some(indices.null_count()),
+        indices.null_buffer().cloned(),
+        indices.offset(),
+        indices.buffers().to_vec(),
+        vec![values],
+    );
+    unsafe { ArrowArray::try_new_full(data, field) }
+}
+
+/// Exports a primitive array whose validity and data buffers are packed in a single
+/// contiguous allocation: the data buffer starts right after the validity buffer, which is
+/// padded to a multiple of 64 bytes. Consumers that copy the array can thus copy both
+/// buffers at once, from the start of the validity buffer to the end of the data buffer.
+///
+/// This copies the buffers of `data`, and the exported array has no offset.
+/// # Error
+/// Errors if `data` is not of a primitive type.
+pub fn export_contiguous(data: ArrayData) -> Result<ArrowArray> {
+    let data_type = data.data_type().clone();
+    if expected_n_buffers(&data_type) != Some(2) || !data.child_data().is_empty() {
+        return Err(ArrowError::CDataInterface(format!(
+            "Only primitive arrays can be exported contiguously, but the array is of type {:?}",
+            data_type
+        )));
+    }
+    let bits = bit_width(&data_type, 1)?;
+    let len = data.len();
+    let offset = data.offset();
+    let null_count = data.null_count();
+
+    let validity_len = bit_util::ceil(len, 8);
+    let padded_validity_len = if null_count > 0 {
+        bit_util::round_upto_multiple_of_64(validity_len)
+    } else {
+        0
+    };
+    let values_len = bit_util::ceil(len * bits, 8);
+
+    let mut buffer = MutableBuffer::new(padded_validity_len + values_len);
+    if let (true, Some(validity)) = (null_count > 0, data.null_buffer()) {
+        let validity = validity.bit_slice(offset, len);
+        buffer.extend_from_slice(&validity.as_slice()[..validity_len]);
+        buffer.extend_zeros(padded_validity_len - validity_len);
+    }
+    let values = &data.buffers()[0];
+    if bits % 8 == 0 {
+        let start = offset * bits / 8;
+        buffer.extend_from_slice(&values.as_slice()[start..start + values_len]);
+    } else {
+        // booleans are bit-packed
+        let values = values.bit_slice(offset, len);
+        buffer.extend_from_slice(&values.as_slice()[..values_len]);
+    }
+    let buffer: Buffer = buffer.into();
+
+    let null_bit_buffer = if null_count > 0 {
+        Some(buffer.clone())
+    } else {
+        None
+    };
+    let data = ArrayData::new(
+        data_type,
+        len,
+        Some(null_count),
+        null_bit_buffer,
+        0,
+        vec![buffer.slice(padded_validity_len)],
+        vec![],
+    );
+    unsafe { ArrowArray::try_new(data) }
+}
+
+/// Exports the rows `[offset, offset + length)` of `batch` as a stream of a single batch,
+/// e.g. to hand over one page of a batch. The columns are sliced without copying them.
+/// # Panics
+/// Panics if `offset + length` is larger than the number of rows of `batch`.
+pub fn export_batch_slice(
+    batch: &RecordBatch,
+    offset: usize,
+    length: usize,
+) -> FFI_ArrowArrayStream {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| column.slice(offset, length))
+        .collect();
+    let slice = RecordBatch::try_new(batch.schema(), columns).unwrap();
+    FFI_ArrowArrayStream::new(Box::new(SingleBatchReader {
+        schema: batch.schema(),
+        batch: Some(slice),
+    }))
+}
+
+// a reader of a single batch, used to export it as a stream
+struct SingleBatchReader {
+    schema: SchemaRef,
+    batch: Option<RecordBatch>,
+}
+
+impl Iterator for SingleBatchReader {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.batch.take().map(Ok)
+    }
+}
+
+impl RecordBatchReader for SingleBatchReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+/// Imports many columns of the same primitive `data_type`, such as the columns of a wide
+/// batch of floats. The format of `data_type` is derived once, and each column is imported
+/// without parsing its format nor building its [Field], which dominate the import of
+/// small columns.
+///
+/// `arrays[i]` is imported with `schemas[i]`, as in [ArrowArray::try_from_raw].
+/// # Safety
+/// Assumes that these pointers represent valid C Data Interfaces, both in memory
+/// representation and lifetime via the `release` mechanism.
+/// # Error
+/// Errors if `data_type` is not primitive, if `arrays` and `schemas` have different lengths,
+/// or if the format of any of the schemas is not the format of `data_type`. Once `data_type`
+/// and the lengths are validated, all columns are owned, and thus released, even on error.
+pub unsafe fn import_primitive_columns(
+    arrays: &[*const FFI_ArrowArray],
+    schemas: &[*const FFI_ArrowSchema],
+    data_type: &DataType,
+) -> Result<Vec<ArrayRef>> {
+    // booleans are bit-packed, and dictionaries have values
+    if expected_n_buffers(data_type) != Some(2)
+        || matches!(data_type, DataType::Boolean | DataType::Dictionary(_, _))
+    {
+        return Err(ArrowError::CDataInterface(format!(
+            "Only columns of a primitive type can be imported together, but the type is {:?}",
+            data_type
+        )));
+    }
+    if arrays.len() != schemas.len() {
+        return Err(ArrowError::CDataInterface(format!(
+            "The number of arrays ({}) differs from the number of schemas ({})",
+            arrays.len(),
+            schemas.len()
+        )));
+    }
+    let format = to_format(data_type)?;
+    let bits = bit_width(data_type, 1)?;
+
+    // take ownership of all columns before validating any, so that they are all released
+    // on error
+    let columns = arrays
+        .iter()
+        .zip(schemas.iter())
+        .map(|(array, schema)| ArrowArray::try_from_raw(*array, *schema))
+        .collect::<Vec<_>>();
+
+    columns
+        .into_iter()
+        .map(|array| {
+            let array = array?;
+            let schema = array.schema();
+            let column_format = schema.try_format()?;
+            if column_format != format || !schema.dictionary.is_null() {
+                return Err(ArrowError::CDataInterface(format!(
+                    "The column \"{}\" has format \"{}\", but \"{}\" was expected",
+                    schema.name(),
+                    column_format,
+                    format
+                )));
+            }
+            let ffi_array = array.array();
+            if ffi_array.n_buffers != 2 {
+                return Err(ArrowError::CDataInterface(format!(
+                    "The column \"{}\" has {} buffers, but 2 were expected",
+                    schema.name(),
+                    ffi_array.n_buffers
+                )));
+            }
+            check_values_alignment(ffi_array, data_type, 1)?;
+            let len = ffi_array.len();
+            // the values before the offset are part of the buffer
+            let values_len = bit_util::ceil((ffi_array.offset() + len) * bits, 8);
+            let values = create_buffer(array.owner().clone(), ffi_array, 1, values_len)
+                .ok_or_else(|| {
+                ArrowError::CDataInterface(format!(
+                    "The values buffer (buffer 1) of the column \"{}\" is null.",
+                    schema.name()
+                ))
+            })?;
+
+            Ok(make_array(ArrayData::new(
+                data_type.clone(),
+                len,
+                Some(ffi_array.null_count()),
+                array.null_bit_buffer(),
+                ffi_array.offset(),
+                vec![values],
+                vec![],
+            )))
+        })
+        .collect()
+}
+
+/// returns the validity buffer, if the array has nulls, and the values of a primitive `array`
+/// of `data_type` exported to the C Data Interface, without importing it. The validity is
+/// indexed from `array.offset()`, while the values start at it.
+/// # Safety
+/// Assumes that `array` and `schema` represent a valid C Data Interface.
+/// # Error
+/// Errors if the type of `schema` is not `data_type`, or if `T` is not the native type of the
+/// values. The timezone of timestamps is ignored, as it does not change their values.
+pub(crate) unsafe fn primitive_slices<'a, T: ArrowNativeType>(
+    array: &'a FFI_ArrowArray,
+    schema: &FFI_ArrowSchema,
+    data_type: &DataType,
+) -> Result<(Option<&'a [u8]>, &'a [T])> {
+    let field = to_field(schema)?;
+    let same_type = match (field.data_type(), data_type) {
+        (DataType::Timestamp(unit, _), DataType::Timestamp(expected, _)) => {
+            unit == expected
+        }
+        (data_type, expected) => data_type == expected,
+    };
+    if !same_type {
+        return Err(ArrowError::CDataInterface(format!(
+            "The array is of type {:?}, but {:?} was expected",
+            field.data_type(),
+            data_type
+        )));
+    }
+    if bit_width(data_type, 1)? != size_of::<T>() * 8 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The values of {:?} are not {}",
+            data_type,
+            std::any::type_name::<T>()
+        )));
+    }
+    if array.length < 0 || array.offset < 0 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The array has a negative length ({}) or offset ({})",
+            array.length, array.offset
+        )));
+    }
+    if array.buffers.is_null() || array.n_buffers != 2 {
+        return Err(ArrowError::CDataInterface(format!(
+            "The array has {} buffers, but 2 were expected",
+            array.n_buffers
+        )));
+    }
+    let buffers = array.buffers as *const *const u8;
+    let (offset, len) = (array.offset(), array.len());
+
+    let values = *buffers.add(1) as *const T;
+    let values = if len == 0 {
+        &[]
+    } else if values.is_null() || (values as usize) % std::mem::align_of::<T>() != 0 {
+        return Err(ArrowError::CDataInterface(
+            "The values buffer (buffer 1) is null or not aligned".to_string(),
+        ));
+    } else {
+        std::slice::from_raw_parts(values.add(offset), len)
+    };
+
+    let validity = *buffers;
+    let validity = if validity.is_null() || array.null_count == 0 {
+        None
+    } else {
+        Some(std::slice::from_raw_parts(
+            validity,
+            bit_util::ceil(offset + len, 8),
+        ))
+    };
+    Ok((validity, values))
+}
+
+/// Exports a single value of `data_type` as an array of length 1, e.g. to pass a scalar to a
+/// function across the C Data Interface, without building an array.
+///
+/// `value` is `None` for a null, or else the bytes of the value:
+/// * the native representation of primitive types, e.g. `42i32.to_ne_bytes()`;
+/// * a single byte, `0` or `1`, for booleans;
+/// * the bytes of the value for binary and utf8 types.
+/// # Error
+/// Errors if `data_type` is not primitive, binary or utf8, or if `value` is not a valid
+/// value of `data_type`, e.g. because its width differs.
+pub fn export_scalar(data_type: &DataType, value: Option<&[u8]>) -> Result<ArrowArray> {
+    let invalid = |reason: String| {
+        ArrowError::CDataInterface(format!(
+            "The value is not a valid scalar of type {:?}: {}",
+            data_type, reason
+        ))
+    };
+    let buffers = match data_type {
+        DataType::Boolean => match value {
+            None | Some([0]) => vec![Buffer::from([0u8])],
+            Some([1]) => vec![Buffer::from([1u8])],
+            Some(value) => {
+                return Err(invalid(format!("{:?} is not a boolean", value)));
+            }
+        },
+        DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Binary
+        | DataType::LargeBinary => {
+            let value = value.unwrap_or_default();
+            if matches!(data_type, DataType::Utf8 | DataType::LargeUtf8) {
+                std::str::from_utf8(value).map_err(|e| invalid(e.to_string()))?;
+            }
+            let offsets = match data_type {
+                DataType::Utf8 | DataType::Binary => {
+                    Buffer::from_slice_ref(&[0i32, value.len() as i32])
+                }
+                _ => Buffer::from_slice_ref(&[0i64, value.len() as i64]),
+            };
+            vec![offsets, Buffer::from(value)]
+        }
+        DataType::Dictionary(_, _) => {
+            return Err(invalid("it is a dictionary".to_string()))
+        }
+        _ if expected_n_buffers(data_type) == Some(2) => {
+            let width = bit_width(data_type, 1)? / 8;
+            match value {
+                None => vec![Buffer::from(vec![0u8; width])],
+                Some(value) if value.len() == width => vec![Buffer::from(value)],
+                Some(value) => {
+                    return Err(invalid(format!(
+                        "it has {} bytes, but {} were expected",
+                        value.len(),
+                        width
+                    )));
+                }
+            }
+        }
+        _ => return Err(invalid("it is not a primitive type".to_string())),
+    };
+
+    let (null_count, null_bit_buffer) = match value {
+        None => (1, Some(Buffer::from([0u8]))),
+        Some(_) => (0, None),
+    };
+    let data = ArrayData::new(
+        data_type.clone(),
+        1,
+        Some(null_count),
+        null_bit_buffer,
+        0,
+        buffers,
+        vec![],
+    );
+    unsafe { ArrowArray::try_new(data) }
+}
+
+impl<'a> ArrowArrayChild<'a> {
+    fn from_raw(
+        array: &'a FFI_ArrowArray,
+        schema: &'a FFI_ArrowSchema,
+        owner: Arc<FFI_ArrowArray>,
+    ) -> Self {
+        Self {
+            array,
+            schema,
+            owner,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::{
+        make_array, Array, ArrayData, ArrayRef, BinaryArray, BinaryOffsetSizeTrait,
+        BooleanArray, Date32Array, Date64Array, DecimalArray, DecimalBuilder,
+        DictionaryArray, DurationMicrosecondArray, DurationMillisecondArray,
+        DurationNanosecondArray, DurationSecondArray, FixedSizeBinaryArray,
+        FixedSizeListArray, Float64Array, GenericBinaryArray, GenericListArray,
+        GenericStringArray, Int32Array, Int32Builder, Int64Array, IntervalDayTimeArray,
+        IntervalYearMonthArray, LargeStringArray, NullArray, OffsetSizeTrait,
+        StringArray, StringOffsetSizeTrait, StructArray, Time32MillisecondArray,
+        TimestampNanosecondArray,
     };
     use crate::compute::kernels;
-    use crate::datatypes::Field;
+    use crate::datatypes::{Field, Int32Type, Int64Type, Int8Type};
+    use crate::ffi_stream::ArrowArrayStreamReader;
     use std::convert::TryFrom;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // serializes the tests reading [live_allocations], a global counter. They only assert
+    // on its changes, as tests not holding the lock may export and release concurrently.
+    #[cfg(feature = "ffi-leak-check")]
+    fn leak_check_lock() -> std::sync::MutexGuard<'static, ()> {
+        lazy_static::lazy_static! {
+            static ref LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        }
+        // a failed test poisons the lock, which must not fail the other tests
+        LOCK.lock().unwrap_or_else(|error| error.into_inner())
+    }
 
     #[test]
     fn test_round_trip() -> Result<()> {
@@ -1017,64 +3622,2640 @@ mod tests {
     }
 
     #[test]
-    fn test_bool() -> Result<()> {
-        // create an array natively
-        let array = BooleanArray::from(vec![None, Some(true), Some(false)]);
+    fn test_from_array() -> Result<()> {
+        let mut builder = Int32Builder::new(3);
+        builder.append_value(1)?;
+        builder.append_null()?;
+        builder.append_value(3)?;
+        let array = builder.finish();
 
         // export it
-        let array = ArrowArray::try_from(array.data().clone())?;
+        let array = unsafe { ArrowArray::from_array(&array) }?;
+        assert!(array.schema.nullable());
 
         // (simulate consumer) import it
         let data = ArrayData::try_from(array)?;
         let array = make_array(data);
 
-        // perform some operation
-        let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
-        let array = kernels::boolean::not(&array)?;
-
         // verify
-        assert_eq!(
-            array,
-            BooleanArray::from(vec![None, Some(false), Some(true)])
-        );
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(array, &Int32Array::from(vec![Some(1), None, Some(3)]));
 
         // (drop/release)
         Ok(())
     }
 
     #[test]
-    fn test_time32() -> Result<()> {
-        // create an array natively
-        let array = Time32MillisecondArray::from(vec![None, Some(1), Some(2)]);
-
-        // export it
+    fn test_check_validity() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
         let array = ArrowArray::try_from(array.data().clone())?;
+        assert!(array.check_validity().is_ok());
+
+        // simulate a producer that swapped the validity and the data buffer
+        unsafe { std::ptr::swap(array.array.buffers, array.array.buffers.add(1)) };
+        assert!(array.check_validity().is_err());
+        assert!(array.to_data_validated().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_all_valid_null_buffer() -> Result<()> {
+        // an all-valid array with a (redundant) validity buffer
+        let data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .null_bit_buffer(Buffer::from([0b00000111]))
+            .add_buffer(Buffer::from_slice_ref(&[1i32, 2, 3]))
+            .build();
+        assert!(data.null_buffer().is_some());
+        let array = ArrowArray::try_from(data)?;
 
         // (simulate consumer) import it
+        let options = ImportOptions {
+            drop_all_valid_null_buffer: true,
+            ..Default::default()
+        };
+        let data = array.to_data_with_options(&options)?;
+        assert!(data.null_buffer().is_none());
+        assert_eq!(data.null_count(), 0);
+
+        let array = make_array(data);
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(array, &Int32Array::from(vec![1, 2, 3]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_keep_null_buffer_with_nulls() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        let options = ImportOptions {
+            drop_all_valid_null_buffer: true,
+            ..Default::default()
+        };
+        let data = array.to_data_with_options(&options)?;
+        assert!(data.null_buffer().is_some());
+        assert_eq!(data.null_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duration_bit_width() {
+        let data_type = DataType::Duration(TimeUnit::Nanosecond);
+        assert_eq!(bit_width(&data_type, 1).unwrap(), 64);
+        assert!(bit_width(&data_type, 2).is_err());
+    }
+
+    #[test]
+    fn test_defer_release() -> Result<()> {
+        let array = Int32Array::from(vec![1, 2, 3]);
+        let (array, released) = counted_export(array.data().clone())?;
+
+        // (simulate consumer) import it, deferring the release to a queue
+        let (array, schema) = ArrowArray::into_raw(array);
+        let queue = Arc::new(std::sync::Mutex::new(vec![]));
+        let sink = queue.clone();
+        let array = unsafe {
+            ArrowArray::try_from_raw(array, schema)?.defer_release(Box::new(
+                move |deferred| sink.lock().unwrap().push(deferred),
+            ))
+        }?;
         let data = ArrayData::try_from(array)?;
         let array = make_array(data);
+        assert_eq!(array.len(), 3);
 
-        // perform some operation
-        let array = kernels::concat::concat(&[array.as_ref(), array.as_ref()]).unwrap();
-        let array = array
-            .as_any()
-            .downcast_ref::<Time32MillisecondArray>()
-            .unwrap();
+        // the release is not called inline on drop...
+        drop(array);
+        assert_eq!(released.load(Ordering::SeqCst), 0);
+        assert_eq!(queue.lock().unwrap().len(), 1);
 
-        // verify
-        assert_eq!(
-            array,
-            &Time32MillisecondArray::from(vec![
-                None,
-                Some(1),
-                Some(2),
-                None,
-                Some(1),
-                Some(2)
-            ])
-        );
+        // ...but when the sink's owner drops the deferred array
+        queue.lock().unwrap().clear();
+        assert_eq!(released.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
 
-        // (drop/release)
+    #[test]
+    fn test_validate_export() -> Result<()> {
+        let array = StringArray::from(vec![Some("a"), None, Some("aaa")]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+        array.validate_export()?;
+
+        let array = StructArray::from(vec![(
+            Field::new("a", DataType::Int32, false),
+            Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+        )]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+        array.validate_export()
+    }
+
+    #[test]
+    fn test_validate_export_corrupted() -> Result<()> {
+        let array = StringArray::from(vec![Some("a"), None, Some("aaa")]);
+
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+        Arc::get_mut(&mut exported.array).unwrap().n_buffers = 2;
+        assert!(exported.validate_export().is_err());
+
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+        Arc::get_mut(&mut exported.array).unwrap().null_count = 4;
+        assert!(exported.validate_export().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_contiguous() -> Result<()> {
+        let array = Int32Array::from(vec![
+            Some(1),
+            Some(2),
+            None,
+            Some(4),
+            Some(5),
+            None,
+            Some(7),
+        ]);
+        let array = array.slice(1, 5);
+
+        let exported = export_contiguous(array.data().clone())?;
+        exported.validate_export()?;
+        let buffers = exported.array.buffers as *const *const u8;
+        let (validity, values) = unsafe { (*buffers, *buffers.add(1)) };
+        assert_eq!(values as usize - validity as usize, 64);
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(exported)?;
+        let array = make_array(data);
+
+        // verify
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        let expected = Int32Array::from(vec![Some(2), None, Some(4), Some(5), None]);
+        assert_eq!(array, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_contiguous_bool() -> Result<()> {
+        let array = BooleanArray::from(vec![None, Some(true), Some(false), Some(true)]);
+        let array = array.slice(1, 3);
+
+        let exported = export_contiguous(array.data().clone())?;
+        let array = make_array(ArrayData::try_from(exported)?);
+
+        let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+        let expected = BooleanArray::from(vec![true, false, true]);
+        assert_eq!(array, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_contiguous_non_primitive() {
+        let array = StringArray::from(vec![Some("a")]);
+        assert!(export_contiguous(array.data().clone()).is_err());
+    }
+
+    #[test]
+    fn test_max_last_offset() -> Result<()> {
+        let array = BinaryArray::from(vec![&b"a"[..]]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // mock an offset buffer whose last offset is the largest possible
+        let offsets = [0i32, i32::MAX];
+        unsafe { *array.array.buffers.add(1) = offsets.as_ptr() as *const _ };
+        assert_eq!(array.buffer_len(1)?, 2 * size_of::<i32>());
+        assert_eq!(array.buffer_len(2)?, i32::MAX as usize);
+
+        // a negative offset is not a valid length
+        let offsets = [0i32, -1];
+        unsafe { *array.array.buffers.add(1) = offsets.as_ptr() as *const _ };
+        assert!(array.buffer_len(2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_field() -> Result<()> {
+        let child = Field::new("item", DataType::Int32, false);
+        let data_type = DataType::List(Box::new(child));
+        let data = ArrayData::builder(data_type.clone())
+            .len(2)
+            .add_buffer(Buffer::from_slice_ref(&[0i32, 1, 3]))
+            .add_child_data(Int32Array::from(vec![1, 2, 3]).data().clone())
+            .build();
+        let field = Field::new("a", data_type.clone(), true);
+        let array = unsafe { ArrowArray::try_new_full(data, field) }?;
+
+        // (simulate consumer) import it
+        let (array, schema) = ArrowArray::into_raw(array);
+        let array = unsafe { ArrowArray::try_from_raw(array, schema) }?;
+
+        let field = array.field()?;
+        assert_eq!(field.name(), "a");
+        assert_eq!(field.data_type(), &data_type);
+        assert!(field.is_nullable());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sliced_bool() -> Result<()> {
+        let array = BooleanArray::from(vec![
+            true, false, false, true, true, false, true, true, false, true,
+        ]);
+        let array = array.slice(4, 6);
+        assert_eq!(array.offset(), 4);
+
+        // export it
+        let array = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(array.buffer_len(1)?, 2);
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+
+        // verify
+        let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(
+            array,
+            &BooleanArray::from(vec![true, false, true, true, false, true])
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "ffi-leak-check")]
+    #[test]
+    fn test_live_allocations() -> Result<()> {
+        let _lock = leak_check_lock();
+        let before = live_allocations();
+        let array = StructArray::from(vec![(
+            Field::new("a", DataType::Utf8, true),
+            Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+        )]);
+
+        // export it
+        let array = ArrowArray::try_from(array.data().clone())?;
+        // the struct and its child, for both the array and the schema
+        assert_eq!(live_allocations() - before, 4);
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+        assert_eq!(array.len(), 2);
+
+        // (drop/release)
+        drop(array);
+        assert_eq!(live_allocations(), before);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reexport_foreign_buffers() -> Result<()> {
+        // a producer whose memory is released when `released` is incremented
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let (array, released) = counted_export(array.data().clone())?;
+
+        // import it: the buffers are now externally owned
+        let data = ArrayData::try_from(array)?;
+
+        // re-export it and drop everything but the export
+        let array = ArrowArray::try_from(data)?;
+        assert_eq!(released.load(Ordering::SeqCst), 0);
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+        assert_eq!(
+            array.as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from(vec![Some(1), None, Some(3)])
+        );
+        assert_eq!(released.load(Ordering::SeqCst), 0);
+
+        // the producer's memory is released only once the consumer releases it
+        drop(array);
+        assert_eq!(released.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_non_utf8() -> Result<()> {
+        // none of these values is valid UTF-8
+        let values: Vec<&[u8]> = vec![&[0xff, 0xfe], &[0xc3, 0x28], &[0x80]];
+        let array = BinaryArray::from(values.clone());
+
+        // export it
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        assert_eq!(data.data_type(), &DataType::Binary);
+        let array = make_array(data);
+
+        // verify
+        let array = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(array, &BinaryArray::from(values));
+
+        // (drop/release)
+        Ok(())
+    }
+
+    #[test]
+    fn test_bool() -> Result<()> {
+        // create an array natively
+        let array = BooleanArray::from(vec![None, Some(true), Some(false)]);
+
+        // export it
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+
+        // perform some operation
+        let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+        let array = kernels::boolean::not(&array)?;
+
+        // verify
+        assert_eq!(
+            array,
+            BooleanArray::from(vec![None, Some(false), Some(true)])
+        );
+
+        // (drop/release)
+        Ok(())
+    }
+
+    #[test]
+    fn test_time32() -> Result<()> {
+        // create an array natively
+        let array = Time32MillisecondArray::from(vec![None, Some(1), Some(2)]);
+
+        // export it
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+
+        // perform some operation
+        let array = kernels::concat::concat(&[array.as_ref(), array.as_ref()]).unwrap();
+        let array = array
+            .as_any()
+            .downcast_ref::<Time32MillisecondArray>()
+            .unwrap();
+
+        // verify
+        assert_eq!(
+            array,
+            &Time32MillisecondArray::from(vec![
+                None,
+                Some(1),
+                Some(2),
+                None,
+                Some(1),
+                Some(2)
+            ])
+        );
+
+        // (drop/release)
+        Ok(())
+    }
+
+    #[test]
+    fn test_date32_extremes() -> Result<()> {
+        // before and after the epoch, including the extremes
+        let values = vec![Some(i32::MIN), Some(-1), None, Some(0), Some(i32::MAX)];
+        let array = Date32Array::from(values.clone());
+
+        // export it
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+
+        // verify
+        let array = array.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(array, &Date32Array::from(values));
+
+        // (drop/release)
+        Ok(())
+    }
+
+    #[test]
+    fn test_date64_extremes() -> Result<()> {
+        // before and after the epoch, including the extremes
+        let values = vec![
+            Some(i64::MIN),
+            Some(-86_400_000),
+            None,
+            Some(0),
+            Some(253_402_300_799_999),
+            Some(i64::MAX),
+        ];
+        let array = Date64Array::from(values.clone());
+
+        // export it
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+
+        // verify
+        let array = array.as_any().downcast_ref::<Date64Array>().unwrap();
+        assert_eq!(array, &Date64Array::from(values));
+
+        // (drop/release)
+        Ok(())
+    }
+
+    #[test]
+    fn test_realign() -> Result<()> {
+        // an Int32 buffer whose start is aligned to `i32`, but not to `ALIGNMENT`
+        let values = Buffer::from_slice_ref(&[0i32, 1, 2, 3]).slice(4);
+        let data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .add_buffer(values)
+            .build();
+
+        // export it
+        let array = ArrowArray::try_from(data)?;
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        assert_ne!(data.buffers()[0].as_ptr() as usize % ALIGNMENT, 0);
+
+        let data = realign(&data);
+        assert_eq!(data.buffers()[0].as_ptr() as usize % ALIGNMENT, 0);
+
+        // perform some operation
+        let array = Int32Array::from(data);
+        let array = kernels::arithmetic::add(&array, &array).unwrap();
+
+        // verify
+        assert_eq!(array, Int32Array::from(vec![2, 4, 6]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_new_full() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+
+        let field = Field::new("a", DataType::Int32, true);
+        let array = unsafe { ArrowArray::try_new_full(array.data().clone(), field)? };
+        assert_eq!(array.schema.name(), "a");
+        assert!(array.schema.nullable());
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        assert_eq!(data.null_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_new_full_non_nullable_with_nulls() {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+
+        let field = Field::new("a", DataType::Int32, false);
+        let result = unsafe { ArrowArray::try_new_full(array.data().clone(), field) };
+        assert!(result.is_err());
+    }
+
+    // exports `data` with a release hook that increments `released`
+    fn counted_export_with(
+        data: ArrayData,
+        released: &Arc<AtomicUsize>,
+    ) -> Result<ArrowArray> {
+        let counter = released.clone();
+        let on_release = Box::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        unsafe { ArrowArray::try_new_with_release_hook(data, on_release) }
+    }
+
+    // exports `data` with a release hook, returning the number of times it was called
+    fn counted_export(data: ArrayData) -> Result<(ArrowArray, Arc<AtomicUsize>)> {
+        let released = Arc::new(AtomicUsize::new(0));
+        let array = counted_export_with(data, &released)?;
+        Ok((array, released))
+    }
+
+    #[test]
+    fn test_release_hook() -> Result<()> {
+        // export it
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let (array, released) = counted_export(array.data().clone())?;
+        let (array, schema) = ArrowArray::into_raw(array);
+        assert_eq!(released.load(Ordering::SeqCst), 0);
+
+        // (simulate consumer) import it
+        let array = unsafe { ArrowArray::try_from_raw(array, schema)? };
+        let data = ArrayData::try_from(array)?;
+        // the imported buffers keep the exported array alive
+        assert_eq!(released.load(Ordering::SeqCst), 0);
+
+        // (drop/release)
+        drop(data);
+        assert_eq!(released.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    // replaces the format of an exported `schema`, releasing the previous one
+    unsafe fn set_format(schema: &mut FFI_ArrowSchema, format: &str) {
+        let private = &mut *(schema.private_data as *mut SchemaPrivateData);
+        private.format = Arc::new(CString::new(format).unwrap());
+        schema.format = private.format.as_ptr();
+    }
+
+    #[test]
+    fn test_check_schema_supported() -> Result<()> {
+        let field = Field::new(
+            "",
+            DataType::Struct(vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, true),
+                Field::new("c", DataType::Boolean, false),
+                Field::new("d", DataType::Int64, false),
+            ]),
+            false,
+        );
+        let schema = FFI_ArrowSchema::try_new(field)?;
+        assert_eq!(check_schema_supported(&schema), Ok(()));
+
+        // simulate a producer using formats that are not supported
+        unsafe {
+            set_format(&mut **schema.children.add(1), "vu");
+            set_format(&mut **schema.children.add(3), "+r");
+        }
+
+        assert_eq!(
+            check_schema_supported(&schema),
+            Err(vec![
+                ("b".to_string(), "vu".to_string()),
+                ("d".to_string(), "+r".to_string())
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_device_array() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+
+        // export it
+        let array = unsafe { ArrowDeviceArray::try_new(array.data().clone()) }?;
+        let (array, schema) = ArrowDeviceArray::into_raw(array);
+
+        // (simulate consumer) import it
+        let array = unsafe { ArrowDeviceArray::try_from_raw(array, schema) }?;
+        assert_eq!(array.device_type(), ARROW_DEVICE_CPU);
+        assert_eq!(array.device_id(), 0);
+        let data = ArrayData::try_from(array.into_array()?)?;
+        let array = make_array(data);
+
+        // verify
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(array, &Int32Array::from(vec![Some(1), None, Some(3)]));
+
+        // (drop/release)
+        Ok(())
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_device_array_double_import() -> Result<()> {
+        let array = Int32Array::from(vec![1, 2, 3]);
+        let array = unsafe { ArrowDeviceArray::try_new(array.data().clone()) }?;
+        let (array_ptr, schema_ptr) = ArrowDeviceArray::into_raw(array);
+
+        let array = unsafe { ArrowDeviceArray::try_from_raw(array_ptr, schema_ptr) }?;
+        assert!(
+            unsafe { ArrowDeviceArray::try_from_raw(array_ptr, schema_ptr) }.is_err()
+        );
+
+        // exporting the array again transfers its ownership back
+        let (array_ptr, schema_ptr) = ArrowDeviceArray::into_raw(array);
+        let array = unsafe { ArrowDeviceArray::try_from_raw(array_ptr, schema_ptr) }?;
+        assert_eq!(ArrayData::try_from(array.into_array()?)?.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_device_array_import_released() {
+        let (array, schema) =
+            ArrowDeviceArray::into_raw(unsafe { ArrowDeviceArray::empty() });
+        assert!(unsafe { ArrowDeviceArray::try_from_raw(array, schema) }.is_err());
+        // the pointers were not imported: release them
+        unsafe {
+            drop(Arc::from_raw(array));
+            drop(Arc::from_raw(schema));
+        }
+    }
+
+    #[test]
+    fn test_device_array_not_cpu() -> Result<()> {
+        let array = Int32Array::from(vec![1, 2, 3]);
+        let mut array = unsafe { ArrowDeviceArray::try_new(array.data().clone()) }?;
+
+        // simulate an array on a CUDA device
+        Arc::get_mut(&mut array.array).unwrap().device_type = 2;
+        assert!(array.into_array().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_list() -> Result<()> {
+        let data = ArrayData::builder(DataType::List(Box::new(Field::new(
+            "item",
+            DataType::Int32,
+            false,
+        ))))
+        .len(2)
+        .add_buffer(Buffer::from_slice_ref(&[0i32, 1, 3]))
+        .add_child_data(Int32Array::from(vec![1, 2, 3]).data().clone())
+        .build();
+        let array = ArrowArray::try_from(data)?;
+
+        let array = array.into_list::<i32>()?;
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.value_length(1), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_large_list() -> Result<()> {
+        let data = ArrayData::builder(DataType::LargeList(Box::new(Field::new(
+            "item",
+            DataType::Int32,
+            false,
+        ))))
+        .len(2)
+        .add_buffer(Buffer::from_slice_ref(&[0i64, 1, 3]))
+        .add_child_data(Int32Array::from(vec![1, 2, 3]).data().clone())
+        .build();
+
+        // a large list is not a list
+        let array = ArrowArray::try_from(data.clone())?;
+        assert!(array.into_list::<i32>().is_err());
+
+        let array = ArrowArray::try_from(data)?;
+        let array = array.into_list::<i64>()?;
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.value_length(1), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_data_projected() -> Result<()> {
+        let columns = (0..5)
+            .map(|i| {
+                (
+                    Field::new(&format!("c{}", i), DataType::Int32, false),
+                    Arc::new(Int32Array::from(vec![i, i + 1])) as ArrayRef,
+                )
+            })
+            .collect::<Vec<_>>();
+        let array = StructArray::from(columns.clone());
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // (simulate consumer) import only two of its columns
+        let data = array.to_data_projected(&[0, 2])?;
+
+        let expected = StructArray::from(vec![columns[0].clone(), columns[2].clone()]);
+        assert_eq!(&data, expected.data());
+
+        assert!(array.to_data_projected(&[5]).is_err());
+        Ok(())
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_double_import() -> Result<()> {
+        let array = Int32Array::from(vec![1, 2, 3]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+        let (array_ptr, schema_ptr) = ArrowArray::into_raw(array);
+
+        let array = unsafe { ArrowArray::try_from_raw(array_ptr, schema_ptr) }?;
+        // e.g. a retry of the import
+        assert!(unsafe { ArrowArray::try_from_raw(array_ptr, schema_ptr) }.is_err());
+
+        // exporting the array again transfers its ownership back
+        let (array_ptr, schema_ptr) = ArrowArray::into_raw(array);
+        let array = unsafe { ArrowArray::try_from_raw(array_ptr, schema_ptr) }?;
+        assert_eq!(ArrayData::try_from(array)?.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_released() {
+        let (array, schema) = ArrowArray::into_raw(unsafe { ArrowArray::empty() });
+        assert!(unsafe { ArrowArray::try_from_raw(array, schema) }.is_err());
+        // the pointers were not imported: release them
+        unsafe {
+            drop(Arc::from_raw(array));
+            drop(Arc::from_raw(schema));
+        }
+    }
+
+    #[test]
+    fn test_large_last_offset() -> Result<()> {
+        let array = LargeStringArray::from(vec![Some("a")]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // mock an offset buffer whose last offset does not fit in an i32
+        let last = i32::MAX as i64 + 10;
+        let offsets = [0i64, last];
+        unsafe { *array.array.buffers.add(1) = offsets.as_ptr() as *const _ };
+        assert_eq!(array.buffer_len(1)?, 2 * size_of::<i64>());
+        assert_eq!(array.buffer_len(2)?, last as usize);
+
+        // the data buffer is created with that length, without reading it
+        let owner = array.owner().clone();
+        let buffer = unsafe { create_buffer(owner, array.array(), 2, last as usize) };
+        assert_eq!(buffer.unwrap().len(), last as usize);
+        Ok(())
+    }
+
+    #[test]
+    fn test_buffer_layout() -> Result<()> {
+        assert_eq!(
+            buffer_layout("u")?,
+            vec![
+                BufferSpec {
+                    name: "validity",
+                    bit_width: 1,
+                    kind: BufferKind::Validity
+                },
+                BufferSpec {
+                    name: "offsets",
+                    bit_width: 32,
+                    kind: BufferKind::Offset
+                },
+                BufferSpec {
+                    name: "data",
+                    bit_width: 8,
+                    kind: BufferKind::Data
+                },
+            ]
+        );
+
+        let layout = buffer_layout("l")?;
+        assert_eq!(layout.len(), 2);
+        assert_eq!(layout[1].bit_width, 64);
+        assert_eq!(layout[1].kind, BufferKind::Data);
+
+        let layout = buffer_layout("+L")?;
+        assert_eq!(layout.len(), 2);
+        assert_eq!(layout[1].bit_width, 64);
+        assert_eq!(layout[1].kind, BufferKind::Offset);
+
+        assert_eq!(buffer_layout("+s")?.len(), 1);
+        assert!(buffer_layout("n")?.is_empty());
+        assert!(buffer_layout("vu").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_data_owned_mutable() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let (array, released) = counted_export(array.data().clone())?;
+
+        // (simulate consumer) import it and release the producer
+        let mut data = array.to_data_owned_mutable()?;
+        drop(array);
+        assert_eq!(released.load(Ordering::SeqCst), 1);
+
+        // modify it in place
+        data.buffers[0].typed_data_mut::<i32>()[2] = 30;
+
+        let array = make_array(data.into_data());
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(array, &Int32Array::from(vec![Some(1), None, Some(30)]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_validity_bit_order() -> Result<()> {
+        // [Some(1), Some(2), None], with a bitmap written most-significant-bit first
+        let data = ArrayData::new(
+            DataType::Int32,
+            3,
+            Some(1),
+            Some(Buffer::from([0b11000000])),
+            0,
+            vec![Buffer::from_slice_ref(&[1i32, 2, 0])],
+            vec![],
+        );
+        let array = ArrowArray::try_from(data)?;
+
+        match array.check_validity() {
+            Err(ArrowError::CDataInterface(message)) => {
+                assert!(message.contains("bit-order mismatch"))
+            }
+            other => panic!("Expected a bit-order mismatch, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_context() -> Result<()> {
+        let field = Field::new(
+            "a",
+            DataType::Struct(vec![
+                Field::new("b", DataType::Int32, true),
+                Field::new("c", DataType::Int32, true),
+            ]),
+            false,
+        );
+        let mut context = ExportContext::new();
+        let schema1 = context.export_schema(field.clone())?;
+        let schema2 = context.export_schema(field.clone())?;
+
+        // the strings are shared
+        assert_eq!(schema1.format, schema2.format);
+        assert_eq!(schema1.child(0).format, schema2.child(1).format);
+        assert_eq!(schema1.name, schema2.name);
+
+        // and outlive the schemas and the context that share them
+        drop(schema1);
+        drop(context);
+        assert_eq!(to_field(&schema2)?, field);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_context_array() -> Result<()> {
+        let mut context = ExportContext::new();
+        for _ in 0..2 {
+            let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+            let array = unsafe { context.export_array(array.data().clone()) }?;
+
+            let array = make_array(ArrayData::try_from(array)?);
+            let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+            assert_eq!(array, &Int32Array::from(vec![Some(1), None, Some(3)]));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_roundtrip() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+
+        let manual = ArrayData::try_from(ArrowArray::try_from(array.data().clone())?)?;
+        let result = roundtrip(array.data().clone())?;
+        assert_eq!(result, manual);
+        assert_eq!(&result, array.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_new_checked() -> Result<()> {
+        let list = |offsets: &[i32]| {
+            ArrayData::builder(DataType::List(Box::new(Field::new(
+                "item",
+                DataType::Int32,
+                false,
+            ))))
+            .len(offsets.len() - 1)
+            .add_buffer(Buffer::from_slice_ref(&offsets))
+            .add_child_data(Int32Array::from(vec![1, 2, 3]).data().clone())
+            .build()
+        };
+
+        let array = unsafe { ArrowArray::try_new_checked(list(&[0, 1, 3])) }?;
+        assert_eq!(ArrayData::try_from(array)?.len(), 2);
+
+        // not monotonic
+        assert!(unsafe { ArrowArray::try_new_checked(list(&[0, 2, 1])) }.is_err());
+        // beyond the child
+        assert!(unsafe { ArrowArray::try_new_checked(list(&[0, 1, 4])) }.is_err());
+
+        let array = StringArray::from(vec![Some("a"), None, Some("aaa")]);
+        unsafe { ArrowArray::try_new_checked(array.data().clone()) }?;
+
+        // misaligned offsets error instead of panicking
+        let offsets = Buffer::from(&[0u8, 0, 0, 0, 0, 1, 0, 0, 0]).slice(1);
+        let array = ArrayData::builder(DataType::Utf8)
+            .len(1)
+            .add_buffer(offsets)
+            .add_buffer(Buffer::from(b"a"))
+            .build();
+        let error = unsafe { ArrowArray::try_new_checked(array) }.unwrap_err();
+        assert!(error.to_string().contains("is not aligned"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_child_by_name() -> Result<()> {
+        let field = Field::new(
+            "",
+            DataType::Struct(vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, true),
+                Field::new("b", DataType::Boolean, false),
+            ]),
+            false,
+        );
+        let schema = FFI_ArrowSchema::try_new(field)?;
+
+        assert_eq!(schema.child_by_name("a")?.format(), "i");
+        // the first match
+        assert_eq!(schema.child_by_name("b")?.format(), "u");
+        assert!(schema.child_by_name("c").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_flags() -> Result<()> {
+        let array = Int32Array::from(vec![1, 2, 3]);
+        let mut array = ArrowArray::try_from(array.data().clone())?;
+        // simulate a producer using a flag of a newer version of the specification
+        Arc::get_mut(&mut array.schema).unwrap().flags |= 1 << 10;
+
+        let options = ImportOptions {
+            strict_flags: true,
+            ..Default::default()
+        };
+        assert!(array.to_data_with_options(&options).is_err());
+
+        // unknown flags are ignored by default
+        assert_eq!(array.to_data()?.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_schema() -> Result<()> {
+        let field = Field::new("a", DataType::Int32, true);
+        let schema = Arc::new(FFI_ArrowSchema::try_new(field)?);
+
+        let arrays = (0..3)
+            .map(|i| {
+                let array = Int32Array::from(vec![Some(i), None]);
+                unsafe {
+                    ArrowArray::try_new_with_schema(array.data().clone(), schema.clone())
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        drop(schema);
+
+        for (i, array) in arrays.into_iter().enumerate() {
+            assert_eq!(array.field()?.name(), "a");
+            let data = array.to_data()?;
+            let array = Int32Array::from(data);
+            assert_eq!(array, Int32Array::from(vec![Some(i as i32), None]));
+        }
+
+        // the schema must match the array
+        let schema = Arc::new(FFI_ArrowSchema::try_new(Field::new(
+            "a",
+            DataType::Utf8,
+            true,
+        ))?);
+        let array = Int32Array::from(vec![1]);
+        assert!(
+            unsafe { ArrowArray::try_new_with_schema(array.data().clone(), schema) }
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_aligned() -> Result<()> {
+        let values = StringArray::from(vec![Some("a"), None, Some("ccc")]);
+        let array = GenericListArray::<i32>::from(ArrayData::new(
+            DataType::List(Box::new(Field::new("item", DataType::Utf8, true))),
+            2,
+            None,
+            None,
+            0,
+            vec![Buffer::from_slice_ref(&[0i32, 1, 3])],
+            vec![values.data().clone()],
+        ));
+        let exported =
+            unsafe { ArrowArray::try_new_aligned(array.data().clone(), 4096) }?;
+
+        fn assert_aligned(array: &FFI_ArrowArray) {
+            for i in 0..array.n_buffers as usize {
+                let buffer = unsafe { *array.buffers.add(i) };
+                if !buffer.is_null() {
+                    assert_eq!(buffer as usize % 4096, 0);
+                }
+            }
+            for i in 0..array.n_children as usize {
+                assert_aligned(unsafe { &**array.children.add(i) });
+            }
+        }
+        assert_aligned(&exported.array);
+
+        let data = exported.to_data()?;
+        assert_eq!(make_array(data).as_ref(), &array as &dyn Array);
+
+        // already aligned buffers are not copied
+        let array = Int32Array::from(vec![1, 2, 3]);
+        let exported =
+            unsafe { ArrowArray::try_new_aligned(array.data().clone(), ALIGNMENT) }?;
+        let buffer = unsafe { *exported.array.buffers.add(1) };
+        assert_eq!(buffer as *const u8, array.data().buffers()[0].as_ptr());
+
+        assert!(unsafe { ArrowArray::try_new_aligned(array.data().clone(), 3) }.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_read_bytes() -> Result<()> {
+        let array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("ccc")]))
+                    as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::LargeUtf8, false),
+                Arc::new(LargeStringArray::from(vec!["dd", "", "e"])) as ArrayRef,
+            ),
+        ]);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        let bytes = exported.import_read_bytes()?;
+
+        fn footprint(data: &ArrayData) -> usize {
+            data.null_buffer().map(|buffer| buffer.len()).unwrap_or(0)
+                + data
+                    .buffers()
+                    .iter()
+                    .map(|buffer| buffer.len())
+                    .sum::<usize>()
+                + data.child_data().iter().map(footprint).sum::<usize>()
+        }
+        assert_eq!(bytes, footprint(&exported.to_data()?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp() -> Result<()> {
+        for timezone in [None, Some("UTC".to_string())].iter() {
+            let array = TimestampNanosecondArray::from_opt_vec(
+                vec![Some(1), None, Some(-1_000_000_000)],
+                timezone.clone(),
+            );
+            let exported = ArrowArray::try_from(array.data().clone())?;
+            let expected = match timezone {
+                Some(_) => "tsn:UTC",
+                None => "tsn:",
+            };
+            assert_eq!(exported.schema.format(), expected);
+
+            let data = ArrayData::try_from(exported)?;
+            assert_eq!(
+                data.data_type(),
+                &DataType::Timestamp(TimeUnit::Nanosecond, timezone.clone())
+            );
+            let imported = TimestampNanosecondArray::from(data);
+            assert_eq!(imported, array);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_duration() -> Result<()> {
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(DurationSecondArray::from(vec![Some(1), None, Some(-1)])),
+            Arc::new(DurationMillisecondArray::from(vec![
+                Some(1),
+                None,
+                Some(-1),
+            ])),
+            Arc::new(DurationMicrosecondArray::from(vec![
+                Some(1),
+                None,
+                Some(-1),
+            ])),
+            Arc::new(DurationNanosecondArray::from(vec![Some(1), None, Some(-1)])),
+        ];
+        for array in arrays {
+            let exported = ArrowArray::try_from(array.data().clone())?;
+            let data = ArrayData::try_from(exported)?;
+            assert_eq!(data.data_type(), array.data_type());
+            assert!(matches!(data.data_type(), DataType::Duration(_)));
+            assert_eq!(make_array(data).as_ref(), array.as_ref());
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "The array has 5 nulls, but its length is 2")]
+    fn test_null_count_larger_than_length() {
+        let data = ArrayData::new(
+            DataType::Int32,
+            2,
+            Some(5),
+            Some(Buffer::from([0b00000000])),
+            0,
+            vec![Buffer::from_slice_ref(&[1i32, 2])],
+            vec![],
+        );
+        let _ = ArrowArray::try_from(data);
+    }
+
+    #[test]
+    fn test_interval() -> Result<()> {
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(IntervalYearMonthArray::from(vec![Some(1), None, Some(-13)])),
+            Arc::new(IntervalDayTimeArray::from(vec![
+                Some(1),
+                None,
+                Some(i64::MAX),
+            ])),
+        ];
+        for array in arrays {
+            let exported = ArrowArray::try_from(array.data().clone())?;
+            let data = ArrayData::try_from(exported)?;
+            assert_eq!(data.data_type(), array.data_type());
+            assert_eq!(make_array(data).as_ref(), array.as_ref());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_float_bit_patterns() -> Result<()> {
+        let values = vec![
+            f64::NAN,
+            -f64::NAN,
+            f64::from_bits(0x7ff0_0000_0000_0001), // a signaling NaN
+            -0.0,
+            0.0,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MIN_POSITIVE / 2.0, // subnormal
+        ];
+        let array = Float64Array::from(values.clone());
+
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        let data = ArrayData::try_from(exported)?;
+        let array = Float64Array::from(data);
+
+        let bits = values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        let imported = array
+            .values()
+            .iter()
+            .map(|v| v.to_bits())
+            .collect::<Vec<_>>();
+        assert_eq!(imported, bits);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal() -> Result<()> {
+        let mut builder = DecimalBuilder::new(3, 20, 4);
+        builder.append_value(1)?;
+        builder.append_null()?;
+        builder.append_value(-12345678901234567890)?;
+        let array = builder.finish();
+
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.schema.format(), "d:20,4");
+        let data = ArrayData::try_from(exported)?;
+        assert_eq!(data.data_type(), &DataType::Decimal(20, 4));
+        assert_eq!(DecimalArray::from(data), array);
+
+        assert_eq!(decimal_data_type("d:20,4,128")?, DataType::Decimal(20, 4));
+        for format in ["d:abc", "d:20", "d:-1,2", "d:20,4,256", "d:20,4,"].iter() {
+            assert!(decimal_data_type(format).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_size_binary() -> Result<()> {
+        let values = vec![Some(b"hello"), None, Some(b"world")];
+        let array = FixedSizeBinaryArray::try_from_sparse_iter(values.into_iter())?;
+
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.schema.format(), "w:5");
+        let data = ArrayData::try_from(exported)?;
+        assert_eq!(data.data_type(), &DataType::FixedSizeBinary(5));
+        assert_eq!(FixedSizeBinaryArray::from(data), array);
+        Ok(())
+    }
+
+    #[test]
+    fn test_buffer_shares_with() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let validity = array.data().null_buffer().unwrap().as_ptr();
+        let values = array.data().buffers()[0].as_ptr();
+
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert!(exported.buffer_shares_with(0, validity));
+        assert!(exported.buffer_shares_with(1, values));
+        assert!(!exported.buffer_shares_with(1, validity));
+        assert!(!exported.buffer_shares_with(2, values));
+
+        // the imported array uses the same memory
+        let data = exported.to_data()?;
+        assert_eq!(data.buffers()[0].as_ptr(), values);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_size_list() -> Result<()> {
+        let values = Int32Array::from(vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let data_type = DataType::FixedSizeList(
+            Box::new(Field::new("item", DataType::Int32, false)),
+            3,
+        );
+        let data = ArrayData::builder(data_type.clone())
+            .len(3)
+            .null_bit_buffer(Buffer::from([0b00000101]))
+            .add_child_data(values.data().clone())
+            .build();
+        let array = FixedSizeListArray::from(data);
+
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.schema.format(), "+w:3");
+        let data = ArrayData::try_from(exported)?;
+        assert_eq!(data.data_type(), &data_type);
+        assert_eq!(FixedSizeListArray::from(data), array);
+        Ok(())
+    }
+
+    #[test]
+    fn test_buffer_report() -> Result<()> {
+        let array = StringArray::from(vec![Some("a"), None, Some("bcd")]);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+
+        let report = exported.buffer_report()?;
+        assert_eq!(report.len(), 3);
+        let lengths = report.iter().map(|info| info.len).collect::<Vec<_>>();
+        // validity, offsets and data
+        assert_eq!(lengths, vec![1, 4 * 4, 4]);
+        assert!(report.iter().all(|info| !info.is_null));
+        assert_eq!(report[0].ptr, array.data().null_buffer().unwrap().as_ptr());
+        assert!(report[1].alignment >= 4);
+        assert_eq!(report[1].ptr as usize % report[1].alignment, 0);
+
+        // no validity buffer
+        let array = StringArray::from(vec!["a"]);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        let report = exported.buffer_report()?;
+        assert!(report[0].is_null);
+        assert_eq!(report[0].len, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary() -> Result<()> {
+        let array = vec![Some("a"), None, Some("b"), Some("a")]
+            .into_iter()
+            .collect::<DictionaryArray<Int8Type>>();
+
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        // the format of the keys, with the values in the dictionary
+        assert_eq!(exported.schema.format(), "c");
+        assert_eq!(exported.schema.n_children, 0);
+        assert_eq!(unsafe { &*exported.schema.dictionary }.format(), "u");
+        assert_eq!(exported.array.n_children, 0);
+        assert!(!exported.array.dictionary.is_null());
+
+        let data = ArrayData::try_from(exported)?;
+        assert_eq!(data.data_type(), array.data_type());
+        let imported = DictionaryArray::<Int8Type>::from(data);
+        assert_eq!(imported.keys(), array.keys());
+        assert_eq!(imported.values().as_ref(), array.values().as_ref());
+        assert_eq!(&imported as &dyn Array, &array as &dyn Array);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_ordered() -> Result<()> {
+        let data_type =
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+        let field = Field::new_dict("a", data_type, true, 0, true);
+        let schema = FFI_ArrowSchema::try_new(field.clone())?;
+        assert_eq!(schema.flags & ARROW_FLAG_DICTIONARY_ORDERED, 1);
+        assert_eq!(to_field(&schema)?, field);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_dictionary() -> Result<()> {
+        let values =
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8));
+        let data_type = DataType::Dictionary(Box::new(DataType::Int32), Box::new(values));
+        let schema = FFI_ArrowSchema::try_new(Field::new("a", data_type, true))?;
+        assert!(to_field(&schema).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_and_fixed_size_binary() -> Result<()> {
+        let binary: ArrayRef = Arc::new(BinaryArray::from(vec![
+            Some(&b"abc"[..]),
+            None,
+            Some(&b"d"[..]),
+        ]));
+        let fixed: ArrayRef = Arc::new(FixedSizeBinaryArray::try_from_sparse_iter(
+            vec![Some(b"ab"), None, Some(b"cd")].into_iter(),
+        )?);
+        let array = StructArray::from(vec![
+            (Field::new("binary", DataType::Binary, true), binary.clone()),
+            (
+                Field::new("fixed", DataType::FixedSizeBinary(2), true),
+                fixed.clone(),
+            ),
+        ]);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.schema.child(0).format(), "z");
+        assert_eq!(exported.schema.child(1).format(), "w:2");
+
+        let data = ArrayData::try_from(exported)?;
+        // offsets and data
+        assert_eq!(data.child_data()[0].buffers().len(), 2);
+        assert_eq!(data.child_data()[0].buffers()[0].len(), 4 * 4);
+        assert_eq!(data.child_data()[0].buffers()[1].len(), 4);
+        // no offsets
+        assert_eq!(data.child_data()[1].buffers().len(), 1);
+        assert_eq!(data.child_data()[1].buffers()[0].len(), 3 * 2);
+
+        let array = StructArray::from(data);
+        assert_eq!(array.column(0).as_ref(), binary.as_ref());
+        assert_eq!(array.column(1).as_ref(), fixed.as_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn test_float16() -> Result<()> {
+        // there is no `Float16Array`: the values are the bits of half-precision floats
+        let values = [0x3c00u16, 0x0000, 0xc000];
+        let data = ArrayData::new(
+            DataType::Float16,
+            3,
+            None,
+            Some(Buffer::from([0b00000101])),
+            0,
+            vec![Buffer::from_slice_ref(&values)],
+            vec![],
+        );
+        let exported = ArrowArray::try_from(data)?;
+        assert_eq!(exported.schema.format(), "e");
+
+        let data = ArrayData::try_from(exported)?;
+        assert_eq!(data.data_type(), &DataType::Float16);
+        assert_eq!(data.null_count(), 1);
+        assert_eq!(unsafe { data.buffers()[0].typed_data::<u16>() }, &values);
+        Ok(())
+    }
+
+    #[test]
+    fn test_null() -> Result<()> {
+        let array = NullArray::new(10);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.array.n_buffers, 0);
+        exported.validate_export()?;
+
+        let data = ArrayData::try_from(exported)?;
+        assert_eq!(data.data_type(), &DataType::Null);
+        assert_eq!(data.len(), 10);
+        assert!(data.buffers().is_empty());
+        assert!(data.null_buffer().is_none());
+        assert_eq!(make_array(data).as_ref(), &array as &dyn Array);
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_on_panic() -> Result<()> {
+        let released = Arc::new(AtomicUsize::new(0));
+        let export = || {
+            let array = StructArray::from(vec![(
+                Field::new("a", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+            )]);
+            counted_export_with(array.data().clone(), &released)
+        };
+
+        // a consumer panicking while holding the imported buffers
+        let array = export()?;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            let data = array.to_data().unwrap();
+            assert_eq!(data.len(), 2);
+            panic!("the consumer panicked");
+        }));
+        assert!(result.is_err());
+        assert_eq!(released.load(Ordering::SeqCst), 1);
+
+        // an import failing midway, on the child
+        let mut array = export()?;
+        unsafe {
+            let schema = Arc::get_mut(&mut array.schema).unwrap();
+            set_format(&mut **schema.children, "vu");
+        }
+        assert!(array.child(0).to_data().is_err());
+        drop(array);
+        assert_eq!(released.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nullable_without_nulls() -> Result<()> {
+        let array = Int32Array::from(vec![1, 2, 3]);
+        assert_eq!(array.null_count(), 0);
+
+        let array = ArrowArray::try_from(array.data().clone())?;
+        assert!(array.schema.nullable());
+
+        // (simulate consumer) import it
+        let (array, schema) = ArrowArray::into_raw(array);
+        let array = unsafe { ArrowArray::try_from_raw(array, schema) }?;
+        assert!(array.field()?.is_nullable());
+        Ok(())
+    }
+
+    #[test]
+    fn test_metadata() -> Result<()> {
+        let metadata = vec![
+            ("encoding".to_string(), "delta".to_string()),
+            ("origin".to_string(), "sensor".to_string()),
+        ]
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+        let mut field = Field::new("a", DataType::Int32, true);
+        field.set_metadata(Some(metadata.clone()));
+
+        let schema = FFI_ArrowSchema::try_new(field.clone())?;
+        assert!(!schema.metadata.is_null());
+        assert_eq!(schema.metadata()?, Some(metadata));
+        assert_eq!(to_field(&schema)?, field);
+
+        // a field without metadata is exported with a null pointer
+        let schema = FFI_ArrowSchema::try_new(Field::new("a", DataType::Int32, true))?;
+        assert!(schema.metadata.is_null());
+        assert_eq!(schema.metadata()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_primitive_columns() -> Result<()> {
+        let columns = (0..3)
+            .map(|i| {
+                let array = Float64Array::from(vec![Some(i as f64), None, Some(2.5)]);
+                let array = ArrowArray::try_from(array.data().clone())?;
+                Ok(ArrowArray::into_raw(array))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (arrays, schemas): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+
+        let imported =
+            unsafe { import_primitive_columns(&arrays, &schemas, &DataType::Float64) }?;
+        assert_eq!(imported.len(), 3);
+        for (i, column) in imported.iter().enumerate() {
+            let column = column.as_any().downcast_ref::<Float64Array>().unwrap();
+            assert_eq!(
+                column,
+                &Float64Array::from(vec![Some(i as f64), None, Some(2.5)])
+            );
+        }
+
+        // a column of another type errors
+        let array = Int32Array::from(vec![1, 2]);
+        let (array, schema) =
+            ArrowArray::into_raw(ArrowArray::try_from(array.data().clone())?);
+        assert!(unsafe {
+            import_primitive_columns(&[array], &[schema], &DataType::Float64)
+        }
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_metadata() -> Result<()> {
+        // empty
+        let encoded = encode_metadata(&HashMap::new());
+        assert_eq!(encoded, 0i32.to_ne_bytes());
+        assert!(decode_metadata_slice(&encoded)?.is_empty());
+        assert!(unsafe { decode_metadata(ptr::null()) }?.is_empty());
+
+        // unicode
+        let metadata = vec![
+            ("encoding".to_string(), "delta".to_string()),
+            ("unit".to_string(), "°C".to_string()),
+            ("名前".to_string(), "データ".to_string()),
+        ]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+        let encoded = encode_metadata(&metadata);
+        assert_eq!(decode_metadata_slice(&encoded)?, metadata);
+        let decoded = unsafe { decode_metadata(encoded.as_ptr() as *const _) }?;
+        assert_eq!(decoded, metadata);
+
+        // truncated
+        for len in 0..encoded.len() {
+            assert!(decode_metadata_slice(&encoded[..len]).is_err());
+        }
+
+        // negative length
+        let mut encoded = 1i32.to_ne_bytes().to_vec();
+        encoded.extend_from_slice(&(-1i32).to_ne_bytes());
+        assert!(decode_metadata_slice(&encoded).is_err());
+
+        // not utf8
+        let mut encoded = 1i32.to_ne_bytes().to_vec();
+        for _ in 0..2 {
+            encoded.extend_from_slice(&1i32.to_ne_bytes());
+            encoded.push(0xff);
+        }
+        assert!(decode_metadata_slice(&encoded).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_offsets_and_values_in_one_allocation() -> Result<()> {
+        // the offsets of ["a", "bb", "", "ccc"], followed by their values
+        let mut bytes = [0i32, 1, 3, 3, 6]
+            .iter()
+            .flat_map(|offset| offset.to_ne_bytes().to_vec())
+            .collect::<Vec<_>>();
+        bytes.extend_from_slice(b"abbccc");
+        let allocation = Buffer::from(bytes);
+        let data = ArrayData::new(
+            DataType::Utf8,
+            4,
+            None,
+            None,
+            0,
+            vec![allocation.clone(), allocation.slice(20)],
+            vec![],
+        );
+
+        // export it
+        let (array, released) = counted_export(data)?;
+        let (array, schema) = ArrowArray::into_raw(array);
+
+        // (simulate consumer) import it
+        let array = unsafe { ArrowArray::try_from_raw(array, schema)? };
+        let data = ArrayData::try_from(array)?;
+        let offsets = data.buffers()[0].as_ptr();
+        let values = data.buffers()[1].as_ptr();
+        assert_eq!(values, unsafe { offsets.add(20) });
+
+        let array = StringArray::from(data);
+        let expected = StringArray::from(vec!["a", "bb", "", "ccc"]);
+        assert_eq!(array, expected);
+
+        // (drop/release) the shared allocation is released once
+        drop(array);
+        assert_eq!(released.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[cfg(feature = "ffi-leak-check")]
+    #[test]
+    fn test_reexport_sliced_foreign_buffers() -> Result<()> {
+        let _lock = leak_check_lock();
+        let before = live_allocations();
+        let array = Int32Array::from(vec![Some(1), None, Some(3), Some(4)]);
+
+        // export it, and import it: the buffers are now owned by the producer's array
+        let array = ArrowArray::try_from(array.data().clone())?;
+        let data = ArrayData::try_from(array)?;
+        assert_eq!(live_allocations() - before, 1);
+
+        // transform it and re-export it: the slice keeps the producer's array alive
+        let sliced = make_array(data).slice(1, 2);
+        let array = ArrowArray::try_from(sliced.data().clone())?;
+        drop(sliced);
+        assert_eq!(live_allocations() - before, 3);
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+        assert_eq!(
+            array.as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from(vec![None, Some(3)])
+        );
+        assert_eq!(live_allocations() - before, 2);
+
+        // (drop/release) both exports are released
+        drop(array);
+        assert_eq!(live_allocations(), before);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_format_suggestion() {
+        let union = DataType::Union(vec![Field::new("a", DataType::Int32, true)]);
+        let error = to_format(&union).unwrap_err().to_string();
+        assert!(error.contains("consider exporting each of its children"));
+
+        let error = to_format(&DataType::Time32(TimeUnit::Nanosecond))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("consider casting to Time64(Nanosecond)"));
+    }
+
+    // a shared memory segment, which is unmapped by releasing it
+    struct MockSharedMemory {
+        unmapped: Arc<AtomicUsize>,
+    }
+
+    impl ImportOwner for MockSharedMemory {
+        fn release(self: Box<Self>) {
+            self.unmapped.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_with_owner() -> Result<()> {
+        // a producer whose array is released when `released` is incremented
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let (array, released) = counted_export(array.data().clone())?;
+        let (array, schema) = ArrowArray::into_raw(array);
+
+        // (simulate consumer) import it, tying it to the segment
+        let unmapped = Arc::new(AtomicUsize::new(0));
+        let owner = Box::new(MockSharedMemory {
+            unmapped: unmapped.clone(),
+        });
+        let array =
+            unsafe { ArrowArray::try_from_raw(array, schema)?.with_owner(owner)? };
+        let data = ArrayData::try_from(array)?;
+        let array = make_array(data);
+        assert_eq!(
+            array.as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from(vec![Some(1), None, Some(3)])
+        );
+        assert_eq!(unmapped.load(Ordering::SeqCst), 0);
+
+        // (drop/release) the producer's array is released before the segment is unmapped
+        drop(array);
+        assert_eq!(released.load(Ordering::SeqCst), 1);
+        assert_eq!(unmapped.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_scalar() -> Result<()> {
+        let array = export_scalar(&DataType::Int32, Some(&42i32.to_ne_bytes()))?;
+        let array = make_array(ArrayData::try_from(array)?);
+        assert_eq!(
+            array.as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from(vec![42])
+        );
+
+        let array = export_scalar(&DataType::Int32, None)?;
+        let array = make_array(ArrayData::try_from(array)?);
+        assert_eq!(
+            array.as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from(vec![None])
+        );
+
+        let array = export_scalar(&DataType::Utf8, Some(b"hello"))?;
+        let array = make_array(ArrayData::try_from(array)?);
+        assert_eq!(
+            array.as_any().downcast_ref::<StringArray>().unwrap(),
+            &StringArray::from(vec!["hello"])
+        );
+
+        let array = export_scalar(&DataType::Boolean, Some(&[1]))?;
+        let array = make_array(ArrayData::try_from(array)?);
+        assert_eq!(
+            array.as_any().downcast_ref::<BooleanArray>().unwrap(),
+            &BooleanArray::from(vec![true])
+        );
+
+        // the width of the value must be the width of the type
+        assert!(export_scalar(&DataType::Int64, Some(&42i32.to_ne_bytes())).is_err());
+        assert!(export_scalar(&DataType::Utf8, Some(&[0xff])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema() -> Result<()> {
+        let metadata = vec![("origin".to_string(), "sensor".to_string())]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        let schema = Schema::new_with_metadata(
+            vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new("b", DataType::Utf8, true),
+                Field::new(
+                    "c",
+                    DataType::List(Box::new(Field::new("item", DataType::Float64, true))),
+                    true,
+                ),
+            ],
+            metadata,
+        );
+
+        let exported = FFI_ArrowSchema::try_from(&schema)?;
+        assert_eq!(exported.format(), "+s");
+        assert_eq!(exported.child(1).name(), "b");
+
+        let imported = Schema::try_from(&exported)?;
+        assert_eq!(imported, schema);
+
+        // a schema is a struct
+        let exported = FFI_ArrowSchema::try_new(Field::new("a", DataType::Int32, true))?;
+        assert!(Schema::try_from(&exported).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_into_raw() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+
+        // (simulate consumer) allocate the structs
+        let mut ffi_array = FFI_ArrowArray::empty();
+        let mut ffi_schema = FFI_ArrowSchema::empty();
+
+        // export it
+        unsafe {
+            export_into_raw(array.data().clone(), &mut ffi_array, &mut ffi_schema)
+        }?;
+        assert_eq!(ffi_array.len(), 3);
+        assert_eq!(ffi_array.null_count(), 1);
+        assert_eq!(ffi_schema.format(), "i");
+
+        // (simulate consumer) import it
+        let imported = ArrowArray {
+            array: Arc::new(ffi_array),
+            schema: Arc::new(ffi_schema),
+        };
+        let data = ArrayData::try_from(imported)?;
+        assert_eq!(Int32Array::from(data), array);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_expecting() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Boolean, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(BooleanArray::from(vec![true, false])),
+            ],
+        )?;
+
+        let (array, ffi_schema) = batch.to_raw()?;
+        let imported = unsafe { import_expecting(array, ffi_schema, &schema) }?;
+        assert_eq!(imported.schema().as_ref(), &schema);
+
+        // the column 2 is not of the expected type
+        let expected = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Int64, true),
+        ]);
+        let (array, ffi_schema) = batch.to_raw()?;
+        let error = unsafe { import_expecting(array, ffi_schema, &expected) }
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("The column 2 is \"c\" of type Boolean"));
+
+        // the mismatched batch was not imported: the caller still owns it
+        drop(unsafe { ArrowArray::try_from_raw(array, ffi_schema) }?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_buffer_order() -> Result<()> {
+        // the pointers of the exported buffers, in order
+        fn exported_buffers(data: &ArrayData) -> Result<Vec<*const u8>> {
+            let exported = ArrowArray::try_from(data.clone())?;
+            let array = exported.array.as_ref();
+            Ok((0..array.n_buffers as usize)
+                .map(|i| unsafe { *array.buffers.add(i) } as *const u8)
+                .collect())
+        }
+
+        let array = StringArray::from(vec![Some("a"), None, Some("bb")]);
+        let data = array.data();
+        assert_eq!(
+            exported_buffers(data)?,
+            vec![
+                data.null_buffer().unwrap().as_ptr(),
+                data.buffers()[0].as_ptr(),
+                data.buffers()[1].as_ptr(),
+            ]
+        );
+
+        // without nulls, the validity buffer is still exported, as a null pointer
+        let array = Int32Array::from(vec![1, 2, 3]);
+        let data = array.data();
+        assert_eq!(
+            exported_buffers(data)?,
+            vec![std::ptr::null(), data.buffers()[0].as_ptr()]
+        );
+
+        let array = NullArray::new(2);
+        assert!(exported_buffers(array.data())?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_wrong_n_buffers() -> Result<()> {
+        fn import_with_n_buffers(data: &ArrayData, n_buffers: i64) -> Result<ArrayData> {
+            let mut exported = ArrowArray::try_from(data.clone())?;
+            Arc::get_mut(&mut exported.array).unwrap().n_buffers = n_buffers;
+            exported.to_data()
+        }
+
+        // primitive
+        let array = Int32Array::from(vec![1, 2]);
+        let error = import_with_n_buffers(array.data(), 3).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("An array of type Int32 must have 2 buffers"));
+
+        // variable-size
+        let array = StringArray::from(vec!["a", "b"]);
+        let error = import_with_n_buffers(array.data(), 2).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("An array of type Utf8 must have 3 buffers"));
+
+        // null
+        let array = NullArray::new(2);
+        let error = import_with_n_buffers(array.data(), 1).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("An array of type Null must have 0 buffers"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_struct_with_unsupported_child() -> Result<()> {
+        let array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, false),
+                Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef,
+            ),
+        ]);
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+
+        // simulate a producer whose second child has a format that is not supported
+        let schema = Arc::get_mut(&mut exported.schema).unwrap();
+        unsafe { set_format(&mut **schema.children.add(1), "vu") };
+
+        match exported.to_data() {
+            Err(ArrowError::CDataInterface(_)) => Ok(()),
+            other => panic!("expected a CDataInterface error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_primitive_value() -> Result<()> {
+        let array = Int64Array::from(vec![None, Some(2), Some(3), None, Some(5)]);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+
+        assert_eq!(exported.primitive_value::<Int64Type>(0)?, None);
+        assert_eq!(exported.primitive_value::<Int64Type>(1)?, Some(2));
+        assert!(exported.primitive_value::<Int64Type>(5).is_err());
+        // the type must match
+        assert!(exported.primitive_value::<Int32Type>(1).is_err());
+
+        // the offset is honored
+        let array = array.slice(2, 3);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.primitive_value::<Int64Type>(0)?, Some(3));
+        assert_eq!(exported.primitive_value::<Int64Type>(1)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_format() -> Result<()> {
+        // a producer that left the format null
+        let schema = FFI_ArrowSchema::empty();
+        assert!(schema.try_format().is_err());
+        assert!(to_field(&schema).is_err());
+
+        // a producer with a non-utf8 format
+        let mut schema =
+            FFI_ArrowSchema::try_new(Field::new("a", DataType::Int32, true))?;
+        let invalid = b"\xff\0";
+        schema.format = invalid.as_ptr() as *const std::os::raw::c_char;
+        assert!(schema.try_format().is_err());
+        assert!(to_field(&schema).is_err());
+
+        // the functions reading the format error instead of panicking
+        let unsupported = check_schema_supported(&schema).unwrap_err();
+        assert_eq!(unsupported.len(), 1);
+        assert!(unsupported[0].1.contains("not valid utf8"));
+
+        let array = StructArray::from(vec![(
+            Field::new("a", DataType::Int32, true),
+            Arc::new(Int32Array::from(vec![1])) as ArrayRef,
+        )]);
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+        Arc::get_mut(&mut exported.schema).unwrap().format =
+            invalid.as_ptr() as *const std::os::raw::c_char;
+        assert!(exported.to_data_projected(&[0]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_validity_aliased_boolean_buffers() -> Result<()> {
+        let array = BooleanArray::from(vec![Some(true), None, Some(true)]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+        assert!(array.check_validity().is_ok());
+
+        // simulate a producer that used the values buffer as validity buffer
+        unsafe { *array.array.buffers = *array.array.buffers.add(1) };
+        let error = array.to_data_validated().unwrap_err();
+        assert!(error.to_string().contains("alias the same memory"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_with_length() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3), Some(4), None]);
+        let values = array.data().buffers()[0].as_ptr();
+
+        let exported =
+            unsafe { ArrowArray::try_new_with_length(array.data().clone(), 3) }?;
+        assert_eq!(exported.array.length, 3);
+        assert_eq!(exported.array.null_count, 1);
+        // the buffers are not sliced
+        assert_eq!(
+            unsafe { *exported.array.buffers.add(1) } as *const u8,
+            values
+        );
+
+        // (simulate consumer) import it
+        let data = ArrayData::try_from(exported)?;
+        let array = make_array(data);
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(array, &Int32Array::from(vec![Some(1), None, Some(3)]));
+
+        assert!(
+            unsafe { ArrowArray::try_new_with_length(array.data().clone(), 4) }.is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_schema_with_nul_byte() {
+        let field = Field::new(
+            "a\0",
+            DataType::Struct(vec![Field::new("b", DataType::Int32, true)]),
+            true,
+        );
+        assert!(FFI_ArrowSchema::try_new(field.clone()).is_err());
+        assert!(ExportContext::new().export_schema(field).is_err());
+    }
+
+    // this test must run on a single thread, see [live_allocations]
+    #[cfg(feature = "ffi-leak-check")]
+    #[test]
+    fn test_export_schema_error_does_not_leak() {
+        let _lock = leak_check_lock();
+        let before = live_allocations();
+        // the last field is not supported, after the others were exported
+        let field = Field::new(
+            "",
+            DataType::Struct(vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Struct(vec![]), true),
+                Field::new(
+                    "c",
+                    DataType::Union(vec![Field::new("a", DataType::Int32, true)]),
+                    true,
+                ),
+            ]),
+            false,
+        );
+        assert!(FFI_ArrowSchema::try_new(field).is_err());
+        assert_eq!(live_allocations(), before);
+
+        // the name is invalid, after all children were exported
+        let field = Field::new(
+            "a\0",
+            DataType::Struct(vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, true),
+            ]),
+            false,
+        );
+        assert!(FFI_ArrowSchema::try_new(field).is_err());
+        assert_eq!(live_allocations(), before);
+    }
+
+    #[test]
+    fn test_import_with_offset() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), Some(2), Some(3), None, Some(5)]);
+        let array = array.slice(2, 3);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.array.offset, 2);
+        assert_eq!(exported.array.length, 3);
+
+        // the values before the offset are part of the buffers
+        assert_eq!(exported.buffer_len(1)?, 5 * 4);
+        let data = exported.to_data()?;
+        assert_eq!(data.buffers()[0].len(), 5 * 4);
+        assert_eq!(data.null_buffer().unwrap().len(), 1);
+
+        // (simulate consumer) import it
+        let array = make_array(data);
+        let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(array, &Int32Array::from(vec![Some(3), None, Some(5)]));
+
+        // the bits before the offset are part of the validity buffer
+        let array = Int32Array::from(
+            (0..20)
+                .map(|i| Some(i).filter(|i| i % 3 != 0))
+                .collect::<Vec<_>>(),
+        );
+        let exported = ArrowArray::try_from(array.slice(10, 5).data().clone())?;
+        let report = exported.buffer_report()?;
+        assert_eq!(report[0].len, 2);
+        assert_eq!(report[1].len, 15 * 4);
+        assert_eq!(exported.to_data()?.null_buffer().unwrap().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_to_many_consumers() -> Result<()> {
+        let array = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let values = array.data().buffers()[0].as_ptr();
+        let array = ArrowArray::try_from(array.data().clone())?;
+        let clone = array.clone();
+        assert!(Arc::ptr_eq(&array.array, &clone.array));
+
+        // export each clone to its own consumer
+        let exported = vec![array, clone]
+            .into_iter()
+            .map(|array| ArrowArray::try_from(ArrayData::try_from(array)?))
+            .collect::<Result<Vec<_>>>()?;
+        assert!(!Arc::ptr_eq(&exported[0].array, &exported[1].array));
+
+        // (simulate consumers) import them: the buffers are the same
+        for array in exported {
+            let (array, schema) = ArrowArray::into_raw(array);
+            let data =
+                ArrayData::try_from(unsafe { ArrowArray::try_from_raw(array, schema) }?)?;
+            assert_eq!(data.buffers()[0].as_ptr(), values);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_misaligned_offsets() -> Result<()> {
+        let array = StringArray::from(vec!["a"]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+
+        // mock a producer whose offset buffer is not aligned for `i32`
+        let offsets = [0i32, 1, 0];
+        let misaligned = unsafe { (offsets.as_ptr() as *const u8).add(1) };
+        unsafe { *array.array.buffers.add(1) = misaligned as *const _ };
+
+        let error = array.buffer_len(2).unwrap_err();
+        assert!(error.to_string().contains("is not aligned"));
+        assert!(array.to_data().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_buffer_errors_report_index() -> Result<()> {
+        // mock a producer whose offset buffer is null
+        let array = StringArray::from(vec!["a"]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+        unsafe { *array.array.buffers.add(1) = ptr::null() };
+        let error = array.buffer_len(2).unwrap_err();
+        assert!(error.to_string().contains("(buffer 1)"));
+
+        // mock a producer whose values buffer is null
+        let array = Int32Array::from(vec![1]);
+        let array = ArrowArray::try_from(array.data().clone())?;
+        unsafe { *array.array.buffers.add(1) = ptr::null() };
+        let error = array.primitive_value::<Int32Type>(0).unwrap_err();
+        assert!(error.to_string().contains("(buffer 1)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_size_binary_invalid_width() -> Result<()> {
+        let mut schema = FFI_ArrowSchema::try_new(Field::new(
+            "a",
+            DataType::FixedSizeBinary(4),
+            true,
+        ))?;
+        assert_eq!(
+            to_field(&schema)?.data_type(),
+            &DataType::FixedSizeBinary(4)
+        );
+
+        // simulate a producer with a width that is not positive
+        for format in &["w:-4", "w:0"] {
+            unsafe { set_format(&mut schema, format) };
+            assert!(to_field(&schema).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_size_list_invalid_schema() -> Result<()> {
+        let data_type = DataType::FixedSizeList(
+            Box::new(Field::new("item", DataType::Int32, false)),
+            3,
+        );
+        let mut schema = FFI_ArrowSchema::try_new(Field::new("a", data_type, true))?;
+
+        // simulate a producer with a negative size
+        unsafe { set_format(&mut schema, "+w:-3") };
+        assert!(to_field(&schema).is_err());
+
+        // simulate a producer that omitted the child
+        unsafe { set_format(&mut schema, "+w:3") };
+        schema.n_children = 0;
+        assert!(to_field(&schema).is_err());
+        schema.n_children = 1;
+        assert!(to_field(&schema).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_extension() -> Result<()> {
+        let values = vec![Some(b"\x01\x02\x03\x04"), None];
+        let array = FixedSizeBinaryArray::try_from_sparse_iter(values.into_iter())?;
+        let mut field = Field::new("location", DataType::FixedSizeBinary(4), true);
+        field.set_metadata(Some(
+            vec![(EXTENSION_NAME_KEY.to_string(), "com.acme.geo".to_string())]
+                .into_iter()
+                .collect(),
+        ));
+        let exported = unsafe { ArrowArray::try_new_full(array.data().clone(), field) }?;
+
+        // (simulate consumer) import it as its storage type
+        let (data, extension_name) = exported.to_data_with_extension()?;
+        assert_eq!(extension_name.as_deref(), Some("com.acme.geo"));
+        assert_eq!(FixedSizeBinaryArray::from(data), array);
+
+        // arrays without extension type
+        let array = Int32Array::from(vec![1, 2]);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.to_data_with_extension()?.1, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_dictionary_array() -> Result<()> {
+        let array = vec![Some("a"), None, Some("b")]
+            .into_iter()
+            .collect::<DictionaryArray<Int8Type>>();
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        let other = ArrowArray::try_from(Int32Array::from(vec![1]).data().clone())?;
+
+        // simulate a producer that set a dictionary on the values of the dictionary
+        let values = unsafe { &mut *exported.array.dictionary };
+        values.dictionary = Arc::as_ptr(&other.array) as *mut FFI_ArrowArray;
+        let error = exported.to_data().unwrap_err();
+        assert!(error.to_string().contains("themselves dictionary-encoded"));
+        values.dictionary = ptr::null_mut();
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_lengths() -> Result<()> {
+        // 100 keys over a dictionary of 5 values
+        let values = ["a", "b", "c", "d", "e"];
+        let array = (0..100)
+            .map(|i| values[i % values.len()])
+            .collect::<DictionaryArray<Int8Type>>();
+
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.array.length, 100);
+        assert_eq!(unsafe { &*exported.array.dictionary }.length, 5);
+
+        let imported = DictionaryArray::<Int8Type>::from(ArrayData::try_from(exported)?);
+        assert_eq!(imported.keys().len(), 100);
+        assert_eq!(imported.values().len(), 5);
+        assert_eq!(&imported as &dyn Array, &array as &dyn Array);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_values_in_children() -> Result<()> {
+        let array = vec![Some("a"), None, Some("b")]
+            .into_iter()
+            .collect::<DictionaryArray<Int8Type>>();
+
+        // the values are in `dictionary`
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert!(exported.to_data().is_ok());
+
+        // simulate a producer that exported the values as the first child
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+        let ffi_array = Arc::get_mut(&mut exported.array).unwrap();
+        let mut children = [ffi_array.dictionary];
+        ffi_array.children = children.as_mut_ptr();
+        ffi_array.n_children = 1;
+        ffi_array.dictionary = ptr::null_mut();
+
+        let error = exported.to_data().unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("must be exported via `dictionary`"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_of_dictionaries() -> Result<()> {
+        let a = vec![Some("x"), None, Some("y"), Some("x")]
+            .into_iter()
+            .collect::<DictionaryArray<Int8Type>>();
+        let b_type =
+            DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Int32));
+        let b = ArrayData::builder(b_type.clone())
+            .len(4)
+            .add_buffer(Buffer::from_slice_ref(&[1i16, 0, 2, 1]))
+            .add_child_data(Int32Array::from(vec![10, 20, 30]).data().clone())
+            .build();
+        let array = StructArray::from(vec![
+            (
+                Field::new("a", a.data_type().clone(), true),
+                Arc::new(a) as ArrayRef,
+            ),
+            (Field::new("b", b_type, false), make_array(b)),
+        ]);
+
+        // each child has its own dictionary
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        let (a_schema, b_schema) = (exported.schema.child(0), exported.schema.child(1));
+        assert_eq!(unsafe { &*a_schema.dictionary }.format(), "u");
+        assert_eq!(unsafe { &*b_schema.dictionary }.format(), "i");
+
+        // (simulate consumer) import it
+        let imported = make_array(ArrayData::try_from(exported)?);
+        let imported = imported.as_any().downcast_ref::<StructArray>().unwrap();
+        assert_eq!(imported, &array);
+        for i in 0..2 {
+            assert_eq!(imported.column(i).data(), array.column(i).data());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_dictionary_is_ordered() -> Result<()> {
+        let array = vec!["b", "a", "b"]
+            .into_iter()
+            .collect::<DictionaryArray<Int8Type>>();
+        let field = Field::new_dict("a", array.data_type().clone(), false, 0, true);
+
+        let exported = unsafe { ArrowArray::try_new_full(array.data().clone(), field)? };
+        let imported = exported.into_dictionary::<Int8Type>()?;
+        assert!(imported.is_ordered());
+        assert_eq!(imported.keys(), array.keys());
+
+        // unordered by default
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert!(!exported.into_dictionary::<Int8Type>()?.is_ordered());
+
+        // wrong key type
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert!(exported.into_dictionary::<Int32Type>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedupe_shared_dictionaries() -> Result<()> {
+        let values = vec!["a", "b", "a"]
+            .into_iter()
+            .collect::<DictionaryArray<Int8Type>>();
+        let list_type = DataType::List(Box::new(Field::new(
+            "item",
+            values.data_type().clone(),
+            true,
+        )));
+        let list = ArrayData::builder(list_type.clone())
+            .len(2)
+            .add_buffer(Buffer::from_slice_ref(&[0i32, 1, 3]))
+            .add_child_data(values.data().clone())
+            .build();
+        let array = StructArray::from(vec![
+            (
+                Field::new("a", list_type.clone(), false),
+                make_array(list.clone()),
+            ),
+            (Field::new("b", list_type, false), make_array(list)),
+        ]);
+
+        // (simulate producer) make the values of both lists point to the same dictionary
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+        unsafe {
+            let struct_ = Arc::get_mut(&mut exported.array).unwrap();
+            let a_values = &**(**struct_.children).children;
+            let b_values = &mut **(**struct_.children.add(1)).children;
+            b_values.dictionary = a_values.dictionary;
+        }
+
+        // (simulate consumer) import it
+        let options = ImportOptions {
+            dedupe_dictionaries: true,
+            ..Default::default()
+        };
+        let imported = exported.to_data_with_options(&options)?;
+        assert_eq!(make_array(imported.clone()).data(), array.data());
+
+        let dictionary = |column: usize| {
+            let values = &imported.child_data()[column].child_data()[0];
+            DictionaryArray::<Int8Type>::from(values.clone())
+        };
+        let (a, b) = (dictionary(0), dictionary(1));
+        assert_eq!(a.values().data(), b.values().data());
+        for (a, b) in a
+            .values()
+            .data()
+            .buffers()
+            .iter()
+            .zip(b.values().data().buffers())
+        {
+            assert_eq!(a.as_ptr(), b.as_ptr());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_batch_slice() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let a = Int32Array::from((0..100).map(Some).collect::<Vec<_>>());
+        let strings = (0..100).map(|i| i.to_string()).collect::<Vec<_>>();
+        let b = StringArray::from(strings.iter().map(String::as_str).collect::<Vec<_>>());
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(a), Arc::new(b)])?;
+
+        // export it
+        let mut stream = export_batch_slice(&batch, 50, 10);
+
+        // (simulate consumer) import it
+        let reader = unsafe { ArrowArrayStreamReader::from_raw(&mut stream) }?;
+        assert_eq!(reader.schema(), schema);
+        let batches = reader.collect::<Result<Vec<_>>>()?;
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 10);
+
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(a, &Int32Array::from((50..60).map(Some).collect::<Vec<_>>()));
+        let b = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let expected = strings[50..60]
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(b, &StringArray::from(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_schema_metadata_levels() -> Result<()> {
+        let mut field = Field::new("t", DataType::Int64, false);
+        let unit = [("unit".to_string(), "s".to_string())];
+        field.set_metadata(Some(unit.iter().cloned().collect()));
+        let version = [("version".to_string(), "2".to_string())];
+        let schema =
+            Schema::new_with_metadata(vec![field], version.iter().cloned().collect());
+
+        // export it
+        let exported = FFI_ArrowSchema::try_from(&schema)?;
+
+        // (simulate consumer) import it
+        let imported = Schema::try_from(&exported)?;
+        assert_eq!(imported, schema);
+        // the top-level metadata belongs to the schema, the child's to its field
+        let expected = version.iter().cloned().collect::<HashMap<_, _>>();
+        assert_eq!(imported.metadata(), &expected);
+        let expected = unit.iter().cloned().collect::<BTreeMap<_, _>>();
+        assert_eq!(imported.field(0).metadata(), &Some(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_extension() -> Result<()> {
+        let json_field = |data_type| {
+            let mut field = Field::new("document", data_type, true);
+            field.set_metadata(Some(
+                vec![(
+                    EXTENSION_NAME_KEY.to_string(),
+                    JSON_EXTENSION_NAME.to_string(),
+                )]
+                .into_iter()
+                .collect(),
+            ));
+            field
+        };
+        let array = StringArray::from(vec![Some(r#"{"a": 1}"#), None]);
+        let field = json_field(DataType::Utf8);
+        let exported = unsafe { ArrowArray::try_new_full(array.data().clone(), field) }?;
+        assert!(exported.schema.is_json()?);
+
+        // (simulate consumer) import it as its storage type
+        let (data, extension_name) = exported.to_data_with_extension()?;
+        assert_eq!(extension_name.as_deref(), Some(JSON_EXTENSION_NAME));
+        assert_eq!(StringArray::from(data), array);
+
+        let array = LargeStringArray::from(vec!["[]"]);
+        let field = json_field(DataType::LargeUtf8);
+        let exported = unsafe { ArrowArray::try_new_full(array.data().clone(), field) }?;
+        assert!(exported.schema.is_json()?);
+
+        // strings without extension type are not JSON
+        let array = StringArray::from(vec!["[]"]);
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert!(!exported.schema.is_json()?);
+
+        // JSON must be stored as strings
+        let array = Int32Array::from(vec![1]);
+        let field = json_field(DataType::Int32);
+        let exported = unsafe { ArrowArray::try_new_full(array.data().clone(), field) }?;
+        assert!(exported.schema.is_json().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_byte_order() -> Result<()> {
+        let value = 12345678901234567890i128;
+        let mut builder = DecimalBuilder::new(1, 20, 0);
+        builder.append_value(value)?;
+        let array = builder.finish();
+
+        // decimals are exported as 16 bytes in little-endian, as in Arrow C++
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        let bytes = unsafe {
+            let values = *(exported.array.buffers as *const *const u8).add(1);
+            std::slice::from_raw_parts(values, 16)
+        };
+        assert_eq!(bytes, &value.to_le_bytes());
+        assert_eq!(
+            bytes,
+            &[0xd2, 0x0a, 0x1f, 0xeb, 0x8c, 0xa9, 0x54, 0xab, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        // (simulate consumer) import it
+        let array = DecimalArray::from(ArrayData::try_from(exported)?);
+        assert_eq!(array.value(0), value);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_day_time_layout() -> Result<()> {
+        // 3 days and 1500 milliseconds: as in Arrow C++ (and the IPC format), a day_time
+        // interval is {days: i32, milliseconds: i32}, i.e. the days are the low 32 bits of
+        // the little-endian i64
+        let value = (1500i64 << 32) | 3;
+        let array = IntervalDayTimeArray::from(vec![Some(value)]);
+
+        // the values are exported as encoded by pyarrow: days then milliseconds
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        assert_eq!(exported.schema.format(), "tiD");
+        let exported_bytes = unsafe {
+            let values = *(exported.array.buffers as *const *const u8).add(1);
+            std::slice::from_raw_parts(values, 8)
+        };
+        assert_eq!(exported_bytes, &[3, 0, 0, 0, 0xdc, 0x05, 0, 0]);
+
+        // (simulate consumer) import it: the fields are read in that order
+        let imported = make_array(ArrayData::try_from(exported)?);
+        assert_eq!(
+            crate::util::display::array_value_to_string(&imported, 0)?,
+            "0 years 0 mons 3 days 0 hours 0 mins 1.500 secs"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_misaligned_values() -> Result<()> {
+        // an Int32 buffer whose start is not aligned to `i32`
+        let values = Buffer::from(&[0u8, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]).slice(1);
+        let data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .add_buffer(values)
+            .build();
+
+        // export it
+        let exported = ArrowArray::try_from(data)?;
+
+        // (simulate consumer) import it
+        let error = ArrayData::try_from(exported).unwrap_err();
+        assert!(error.to_string().contains("is not aligned to the 4 bytes"));
+
+        // values read byte by byte need no alignment
+        let values = Buffer::from(&[0u8, 1, 2, 3, 4]).slice(1);
+        let data = ArrayData::builder(DataType::FixedSizeBinary(2))
+            .len(2)
+            .add_buffer(values)
+            .build();
+        let exported = ArrowArray::try_from(data)?;
+        let array = FixedSizeBinaryArray::from(ArrayData::try_from(exported)?);
+        assert_eq!(array.value(1), &[3, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_primitive_columns_error_releases() -> Result<()> {
+        let released = Arc::new(AtomicUsize::new(0));
+        let column = |data: ArrayData| -> Result<_> {
+            Ok(ArrowArray::into_raw(counted_export_with(data, &released)?))
+        };
+        let columns = vec![
+            column(Float64Array::from(vec![1.0]).data().clone())?,
+            // a column of another type errors
+            column(Int32Array::from(vec![1]).data().clone())?,
+            column(Float64Array::from(vec![2.0]).data().clone())?,
+        ];
+        let (arrays, schemas): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+
+        assert!(unsafe {
+            import_primitive_columns(&arrays, &schemas, &DataType::Float64)
+        }
+        .is_err());
+        // all columns were released, including those after the invalid one
+        assert_eq!(released.load(Ordering::SeqCst), 3);
+
+        // a null values buffer is reported at its index
+        let array = Float64Array::from(vec![1.0]);
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+        let ffi_array = Arc::get_mut(&mut exported.array).unwrap();
+        let buffers = ffi_array.buffers as *mut *const std::os::raw::c_void;
+        unsafe { *buffers.add(1) = ptr::null() };
+        let (array, schema) = ArrowArray::into_raw(exported);
+        let error =
+            unsafe { import_primitive_columns(&[array], &[schema], &DataType::Float64) }
+                .unwrap_err();
+        assert!(error.to_string().contains("buffer 1"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_schema_without_metadata() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let exported = FFI_ArrowSchema::try_from(&schema)?;
+        assert!(exported.metadata.is_null());
+        assert_eq!(Schema::try_from(&exported)?, schema);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_dictionary() -> Result<()> {
+        let indices = Int32Array::from(vec![Some(1), None, Some(0), Some(1)]);
+        let values = StringArray::from(vec!["a", "b"]);
+
+        // export it
+        let exported =
+            export_dictionary(indices.data().clone(), values.data().clone(), true)?;
+        assert_eq!(exported.schema.format(), "i");
+        assert_eq!(unsafe { &*exported.schema.dictionary }.format(), "u");
+
+        // (simulate consumer) import it
+        let array = exported.into_dictionary::<Int32Type>()?;
+        assert_eq!(array.keys(), &indices);
+        assert_eq!(array.values().data(), values.data());
+        assert!(array.is_ordered());
+
+        // unordered
+        let exported =
+            export_dictionary(indices.data().clone(), values.data().clone(), false)?;
+        assert!(!exported.into_dictionary::<Int32Type>()?.is_ordered());
+
+        // indices must be integers
+        let indices = Float64Array::from(vec![1.0]);
+        assert!(
+            export_dictionary(indices.data().clone(), values.data().clone(), false)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_export_corrupted_dictionary() -> Result<()> {
+        let array = vec![Some("a"), None, Some("a")]
+            .into_iter()
+            .collect::<DictionaryArray<Int8Type>>();
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        exported.validate_export()?;
+
+        // the values are invalid
+        let exported = ArrowArray::try_from(array.data().clone())?;
+        unsafe { (*exported.array.dictionary).null_count = 4 };
+        assert!(exported.validate_export().is_err());
+
+        // the values are missing (they are still released, via the private data)
+        let mut exported = ArrowArray::try_from(array.data().clone())?;
+        Arc::get_mut(&mut exported.array).unwrap().dictionary = ptr::null_mut();
+        assert!(exported.validate_export().is_err());
         Ok(())
     }
 }
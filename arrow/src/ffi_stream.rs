@@ -0,0 +1,637 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Contains declarations to bind to the [C Stream Interface](https://arrow.apache.org/docs/format/CStreamInterface.html).
+//!
+//! A stream hands over a sequence of [RecordBatch](crate::record_batch::RecordBatch)es
+//! sharing the same schema, e.g. a table. Each batch is exported as a struct array via the
+//! [C Data Interface](crate::ffi).
+//!
+//! A [RecordBatchReader] is exported via [FFI_ArrowArrayStream::new] or
+//! [export_reader_into_stream]. Its batches are only read when the consumer requests them,
+//! via `get_next`. Conversely, a stream is imported as a [RecordBatchReader] via
+//! [ArrowArrayStreamReader::from_raw].
+
+/*
+# Design:
+
+`FFI_ArrowArrayStream` is ABI-compatible with `ArrowArrayStream`. Like `FFI_ArrowArray`,
+its `private_data` owns everything the stream needs, here the exported reader and the
+last error, and is freed by its `release` callback.
+
+Errors of the reader are returned to the consumer as an `errno`-compatible code, and their
+message is kept in the private data until the next error, as required by `get_last_error`.
+*/
+
+use std::{
+    convert::TryFrom,
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_int, c_void},
+    ptr,
+    sync::Arc,
+};
+
+use crate::array::{Array, ArrayData, StructArray};
+use crate::datatypes::{Schema, SchemaRef};
+use crate::error::{ArrowError, Result};
+use crate::ffi::{ArrowArray, FFI_ArrowArray, FFI_ArrowSchema};
+use crate::record_batch::{RecordBatch, RecordBatchReader};
+
+// `errno` codes returned by the callbacks, see
+// <https://arrow.apache.org/docs/format/CStreamInterface.html#error-handling>
+const EIO: c_int = 5;
+const ENOMEM: c_int = 12;
+const EINVAL: c_int = 22;
+
+/// ABI-compatible struct for `ArrowArrayStream` from C Stream Interface
+/// See <https://arrow.apache.org/docs/format/CStreamInterface.html#structure-definitions>
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFI_ArrowArrayStream {
+    get_schema: Option<
+        unsafe extern "C" fn(
+            arg1: *mut FFI_ArrowArrayStream,
+            out: *mut FFI_ArrowSchema,
+        ) -> c_int,
+    >,
+    get_next: Option<
+        unsafe extern "C" fn(
+            arg1: *mut FFI_ArrowArrayStream,
+            out: *mut FFI_ArrowArray,
+        ) -> c_int,
+    >,
+    get_last_error:
+        Option<unsafe extern "C" fn(arg1: *mut FFI_ArrowArrayStream) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(arg1: *mut FFI_ArrowArrayStream)>,
+    private_data: *mut c_void,
+}
+
+struct StreamPrivateData {
+    batch_reader: Box<dyn RecordBatchReader>,
+    // the message of the last error, returned by `get_last_error`
+    last_error: Option<CString>,
+}
+
+impl StreamPrivateData {
+    // stores the message of `error`, and returns its `errno` code
+    fn set_error(&mut self, error: ArrowError) -> c_int {
+        let code = match error {
+            ArrowError::IoError(_) => EIO,
+            ArrowError::MemoryError(_) => ENOMEM,
+            _ => EINVAL,
+        };
+        // the message can't contain nul bytes, as it is returned as a C string
+        let message = error.to_string().replace('\0', "");
+        self.last_error = Some(CString::new(message).unwrap());
+        code
+    }
+}
+
+// callback used to get the schema of the batches of [FFI_ArrowArrayStream] when it is exported.
+unsafe extern "C" fn get_schema(
+    stream: *mut FFI_ArrowArrayStream,
+    out: *mut FFI_ArrowSchema,
+) -> c_int {
+    let private = &mut *((*stream).private_data as *mut StreamPrivateData);
+    match export_schema(private.batch_reader.schema()) {
+        Ok(schema) => {
+            // `out` may be uninitialized: it must not be dropped
+            ptr::write(out, schema);
+            0
+        }
+        Err(error) => private.set_error(error),
+    }
+}
+
+// callback used to get the next batch of [FFI_ArrowArrayStream] when it is exported.
+// The end of the stream is signaled with a released array.
+unsafe extern "C" fn get_next(
+    stream: *mut FFI_ArrowArrayStream,
+    out: *mut FFI_ArrowArray,
+) -> c_int {
+    let private = &mut *((*stream).private_data as *mut StreamPrivateData);
+    match private.batch_reader.next() {
+        None => {
+            ptr::write(out, FFI_ArrowArray::empty());
+            0
+        }
+        Some(Ok(batch)) => {
+            let array = StructArray::from(batch);
+            ptr::write(out, FFI_ArrowArray::new(array.data()));
+            0
+        }
+        Some(Err(error)) => private.set_error(error),
+    }
+}
+
+// callback used to get the message of the last error of [FFI_ArrowArrayStream] when it is
+// exported. The message is valid until the next call to any of the callbacks.
+unsafe extern "C" fn get_last_error(stream: *mut FFI_ArrowArrayStream) -> *const c_char {
+    let private = &*((*stream).private_data as *const StreamPrivateData);
+    private
+        .last_error
+        .as_ref()
+        .map(|error| error.as_ptr())
+        .unwrap_or_else(ptr::null)
+}
+
+// callback used to drop [FFI_ArrowArrayStream] when it is exported.
+unsafe extern "C" fn release_stream(stream: *mut FFI_ArrowArrayStream) {
+    if stream.is_null() {
+        return;
+    }
+    let stream = &mut *stream;
+
+    // take ownership back to release it, which drops the reader.
+    let _ = Box::from_raw(stream.private_data as *mut StreamPrivateData);
+
+    stream.get_schema = None;
+    stream.get_next = None;
+    stream.get_last_error = None;
+    stream.release = None;
+}
+
+// the batches are exported as struct arrays, whose schema carries the schema's metadata
+fn export_schema(schema: SchemaRef) -> Result<FFI_ArrowSchema> {
+    FFI_ArrowSchema::try_from(schema.as_ref())
+}
+
+impl FFI_ArrowArrayStream {
+    /// creates a new [FFI_ArrowArrayStream] that exports the batches of `batch_reader`.
+    /// The reader is only advanced when the consumer requests the next batch, and is
+    /// dropped when the stream is released.
+    pub fn new(batch_reader: Box<dyn RecordBatchReader>) -> Self {
+        let private_data = Box::new(StreamPrivateData {
+            batch_reader,
+            last_error: None,
+        });
+
+        Self {
+            get_schema: Some(get_schema),
+            get_next: Some(get_next),
+            get_last_error: Some(get_last_error),
+            release: Some(release_stream),
+            private_data: Box::into_raw(private_data) as *mut c_void,
+        }
+    }
+
+    /// create an empty [FFI_ArrowArrayStream], which can be used to import a stream into
+    pub fn empty() -> Self {
+        Self {
+            get_schema: None,
+            get_next: None,
+            get_last_error: None,
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+
+    /// whether this stream was released
+    pub fn is_released(&self) -> bool {
+        self.release.is_none()
+    }
+}
+
+/// Exports `reader` to the C Stream Interface, writing the stream into `out`. The consumer
+/// owns the stream, and must release it once it is done with it.
+/// # Safety
+/// `out` must be valid for writes. Its previous content, if any, is overwritten without being
+/// released.
+pub unsafe fn export_reader_into_stream(
+    reader: Box<dyn RecordBatchReader>,
+    out: *mut FFI_ArrowArrayStream,
+) {
+    ptr::write(out, FFI_ArrowArrayStream::new(reader))
+}
+
+impl Drop for FFI_ArrowArrayStream {
+    fn drop(&mut self) {
+        match self.release {
+            None => (),
+            Some(release) => unsafe { release(self) },
+        };
+    }
+}
+
+/// A [RecordBatchReader] of the batches of an imported [FFI_ArrowArrayStream]. The schema of
+/// the stream is imported once, and each batch is imported from the struct array returned by
+/// `get_next`. The stream is released when this reader is dropped.
+#[derive(Debug)]
+pub struct ArrowArrayStreamReader {
+    stream: Box<FFI_ArrowArrayStream>,
+    // the exported schema of the batches, shared by all imported arrays
+    ffi_schema: Arc<FFI_ArrowSchema>,
+    schema: SchemaRef,
+}
+
+impl ArrowArrayStreamReader {
+    /// Imports the stream pointed to by `stream`, taking ownership of it: `stream` is left
+    /// released, so that its producer's callbacks are only called by this reader.
+    /// # Safety
+    /// Assumes that `stream` points to a valid C Stream Interface, both in memory
+    /// representation and lifetime via the `release` mechanism.
+    /// # Error
+    /// Errors if `stream` is null or released, or if its schema can't be imported as the
+    /// schema of record batches, i.e. as a struct.
+    pub unsafe fn from_raw(stream: *mut FFI_ArrowArrayStream) -> Result<Self> {
+        if stream.is_null() {
+            return Err(ArrowError::MemoryError(
+                "The pointer passed to `from_raw` is null".to_string(),
+            ));
+        }
+        if (*stream).release.is_none() {
+            return Err(ArrowError::CDataInterface(
+                "The stream passed to `from_raw` is released".to_string(),
+            ));
+        }
+        let mut stream = Box::new(ptr::replace(stream, FFI_ArrowArrayStream::empty()));
+
+        let mut ffi_schema = FFI_ArrowSchema::empty();
+        let get_schema = stream.get_schema.ok_or_else(|| {
+            ArrowError::CDataInterface("The stream has no `get_schema`".to_string())
+        })?;
+        let code = get_schema(stream.as_mut(), &mut ffi_schema);
+        if code != 0 {
+            return Err(last_error(&mut stream, code));
+        }
+
+        let schema = Schema::try_from(&ffi_schema)?;
+
+        Ok(Self {
+            stream,
+            ffi_schema: Arc::new(ffi_schema),
+            schema: Arc::new(schema),
+        })
+    }
+
+    // imports the next batch of the stream, or `None` at the end of the stream
+    unsafe fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        let get_next = self.stream.get_next.ok_or_else(|| {
+            ArrowError::CDataInterface("The stream has no `get_next`".to_string())
+        })?;
+        // the array is imported as `ArrowArray::try_from_raw` expects: from an `Arc`
+        let array =
+            Arc::into_raw(Arc::new(FFI_ArrowArray::empty())) as *mut FFI_ArrowArray;
+        let code = get_next(self.stream.as_mut(), array);
+        if code != 0 || (*array).release.is_none() {
+            drop(Arc::from_raw(array));
+            if code != 0 {
+                return Err(last_error(&mut self.stream, code));
+            }
+            // a released array signals the end of the stream
+            return Ok(None);
+        }
+
+        let schema = Arc::into_raw(self.ffi_schema.clone());
+        let array = ArrowArray::try_from_raw(array, schema)?;
+        let array = StructArray::from(ArrayData::try_from(array)?);
+        Ok(Some(RecordBatch::from(&array)))
+    }
+}
+
+// returns the error of the failed call of `stream` that returned `code`
+unsafe fn last_error(stream: &mut FFI_ArrowArrayStream, code: c_int) -> ArrowError {
+    let message = match stream.get_last_error {
+        Some(get_last_error) => {
+            let message = get_last_error(stream);
+            if message.is_null() {
+                "no error message".to_string()
+            } else {
+                CStr::from_ptr(message).to_string_lossy().into_owned()
+            }
+        }
+        None => "no error message".to_string(),
+    };
+    ArrowError::CDataInterface(format!(
+        "The stream failed with code {}: {}",
+        code, message
+    ))
+}
+
+impl Iterator for ArrowArrayStreamReader {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe { self.next_batch() }.transpose()
+    }
+}
+
+impl RecordBatchReader for ArrowArrayStreamReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::array::{ArrayRef, Int32Array};
+    use crate::datatypes::{DataType, Field};
+
+    // a reader of `batches`, counting the batches read so far in `read`
+    struct TestReader {
+        schema: SchemaRef,
+        batches: std::vec::IntoIter<Result<RecordBatch>>,
+        read: Arc<AtomicUsize>,
+    }
+
+    impl Iterator for TestReader {
+        type Item = Result<RecordBatch>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let batch = self.batches.next();
+            if batch.is_some() {
+                self.read.fetch_add(1, Ordering::SeqCst);
+            }
+            batch
+        }
+    }
+
+    impl RecordBatchReader for TestReader {
+        fn schema(&self) -> SchemaRef {
+            self.schema.clone()
+        }
+    }
+
+    fn test_reader(
+        batches: Vec<Result<RecordBatch>>,
+    ) -> (Box<dyn RecordBatchReader>, Arc<AtomicUsize>) {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let read = Arc::new(AtomicUsize::new(0));
+        let reader = TestReader {
+            schema,
+            batches: batches.into_iter(),
+            read: read.clone(),
+        };
+        (Box::new(reader), read)
+    }
+
+    fn test_batch(values: Vec<Option<i32>>) -> Result<RecordBatch> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let column = Arc::new(Int32Array::from(values)) as ArrayRef;
+        RecordBatch::try_new(schema, vec![column])
+    }
+
+    #[test]
+    fn test_stream() -> Result<()> {
+        let batches = vec![
+            test_batch(vec![Some(1), None, Some(3)]),
+            test_batch(vec![Some(4)]),
+        ];
+        let (reader, read) = test_reader(batches);
+        let mut stream = FFI_ArrowArrayStream::new(reader);
+
+        // the schema is a struct with the fields of the batches
+        let mut schema = FFI_ArrowSchema::empty();
+        let code = unsafe { get_schema(&mut stream, &mut schema) };
+        assert_eq!(code, 0);
+        assert_eq!(schema.format(), "+s");
+        assert_eq!(schema.child(0).name(), "a");
+        assert_eq!(schema.child(0).format(), "i");
+
+        // the batches are read lazily
+        assert_eq!(read.load(Ordering::SeqCst), 0);
+        for expected_len in &[3, 1] {
+            let mut array = FFI_ArrowArray::empty();
+            let code = unsafe { get_next(&mut stream, &mut array) };
+            assert_eq!(code, 0);
+            assert_eq!(array.len(), *expected_len);
+            assert_eq!(array.n_children, 1);
+        }
+        assert_eq!(read.load(Ordering::SeqCst), 2);
+
+        // the end of the stream is a released array
+        let mut array = FFI_ArrowArray::empty();
+        let code = unsafe { get_next(&mut stream, &mut array) };
+        assert_eq!(code, 0);
+        assert!(array.release.is_none());
+
+        // (drop/release)
+        drop(stream);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_error() -> Result<()> {
+        let batches = vec![Err(ArrowError::IoError("disconnected".to_string()))];
+        let (reader, _) = test_reader(batches);
+        let mut stream = FFI_ArrowArrayStream::new(reader);
+
+        // no error yet
+        assert!(unsafe { get_last_error(&mut stream) }.is_null());
+
+        let mut array = FFI_ArrowArray::empty();
+        let code = unsafe { get_next(&mut stream, &mut array) };
+        assert_eq!(code, EIO);
+        let message = unsafe { CStr::from_ptr(get_last_error(&mut stream)) };
+        assert!(message.to_str().unwrap().contains("disconnected"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_stream() -> Result<()> {
+        let (reader, _) = test_reader(vec![]);
+        let mut stream = FFI_ArrowArrayStream::new(reader);
+        assert!(!stream.is_released());
+
+        unsafe { release_stream(&mut stream) };
+        assert!(stream.is_released());
+        assert!(stream.get_next.is_none());
+
+        // dropping a released stream does not release it again
+        drop(stream);
+        assert!(FFI_ArrowArrayStream::empty().is_released());
+        Ok(())
+    }
+
+    // (simulate consumer) reads all batches of `stream` via its callbacks
+    unsafe fn import_batches(
+        stream: *mut FFI_ArrowArrayStream,
+    ) -> Result<Vec<RecordBatch>> {
+        let mut batches = vec![];
+        loop {
+            let (array, schema) = ArrowArray::into_raw(ArrowArray::empty());
+            let code = (*stream).get_schema.unwrap()(stream, schema as *mut _);
+            assert_eq!(code, 0);
+            let code = (*stream).get_next.unwrap()(stream, array as *mut _);
+            assert_eq!(code, 0);
+
+            if (*array).release.is_none() {
+                // the end of the stream: the consumer still owns the empty structs
+                drop(Arc::from_raw(array));
+                drop(Arc::from_raw(schema));
+                return Ok(batches);
+            }
+            let data = ArrayData::try_from(ArrowArray::try_from_raw(array, schema)?)?;
+            batches.push(RecordBatch::from(&StructArray::from(data)));
+        }
+    }
+
+    #[test]
+    fn test_export_reader_into_stream() -> Result<()> {
+        let values = vec![
+            vec![Some(1), None, Some(3)],
+            vec![Some(4)],
+            vec![None, Some(6)],
+        ];
+        let batches = values
+            .iter()
+            .map(|values| test_batch(values.clone()))
+            .collect::<Vec<_>>();
+        let (reader, _) = test_reader(batches);
+
+        // export it
+        let mut stream = FFI_ArrowArrayStream::empty();
+        unsafe { export_reader_into_stream(reader, &mut stream) };
+
+        // (simulate consumer) import it
+        let batches = unsafe { import_batches(&mut stream) }?;
+        assert_eq!(batches.len(), 3);
+        for (batch, values) in batches.iter().zip(values) {
+            assert_eq!(batch.num_columns(), 1);
+            assert_eq!(batch.schema().field(0).name(), "a");
+            let column = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            assert_eq!(column, &Int32Array::from(values));
+        }
+
+        // (drop/release)
+        drop(stream);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_reader() -> Result<()> {
+        let values = vec![vec![Some(1), None, Some(3)], vec![Some(4)]];
+        let batches = values
+            .iter()
+            .map(|values| test_batch(values.clone()))
+            .collect::<Vec<_>>();
+        let (reader, read) = test_reader(batches);
+
+        // export it
+        let mut stream = FFI_ArrowArrayStream::new(reader);
+
+        // (simulate consumer) import it
+        let reader = unsafe { ArrowArrayStreamReader::from_raw(&mut stream) }?;
+        // the reader owns the stream
+        assert!(stream.is_released());
+        assert_eq!(reader.schema().field(0).name(), "a");
+        assert_eq!(reader.schema().field(0).data_type(), &DataType::Int32);
+
+        let batches = reader.collect::<Result<Vec<_>>>()?;
+        assert_eq!(read.load(Ordering::SeqCst), 2);
+        assert_eq!(batches.len(), 2);
+        for (batch, values) in batches.iter().zip(values) {
+            let column = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            assert_eq!(column, &Int32Array::from(values));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_reader_error() -> Result<()> {
+        let batches = vec![
+            test_batch(vec![Some(1)]),
+            Err(ArrowError::IoError("disconnected".to_string())),
+        ];
+        let (reader, _) = test_reader(batches);
+        let mut stream = FFI_ArrowArrayStream::new(reader);
+
+        let mut reader = unsafe { ArrowArrayStreamReader::from_raw(&mut stream) }?;
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next() {
+            Some(Err(ArrowError::CDataInterface(message))) => {
+                assert!(message.contains(&format!("code {}", EIO)));
+                assert!(message.contains("disconnected"));
+            }
+            other => panic!("expected an error, got {:?}", other),
+        }
+
+        // a released stream can't be imported
+        let mut stream = FFI_ArrowArrayStream::empty();
+        assert!(unsafe { ArrowArrayStreamReader::from_raw(&mut stream) }.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_backpressure() -> Result<()> {
+        let batches = (0..3).map(|i| test_batch(vec![Some(i)])).collect();
+        let (reader, read) = test_reader(batches);
+        let mut stream = FFI_ArrowArrayStream::new(reader);
+
+        // exporting the stream and its schema does not read any batch
+        let mut schema = FFI_ArrowSchema::empty();
+        assert_eq!(unsafe { get_schema(&mut stream, &mut schema) }, 0);
+        assert_eq!(read.load(Ordering::SeqCst), 0);
+
+        // each call to `get_next` reads exactly one batch, and none ahead
+        for i in 1..=3 {
+            let mut array = FFI_ArrowArray::empty();
+            assert_eq!(unsafe { get_next(&mut stream, &mut array) }, 0);
+            assert_eq!(read.load(Ordering::SeqCst), i);
+        }
+
+        // the end of the stream
+        let mut array = FFI_ArrowArray::empty();
+        assert_eq!(unsafe { get_next(&mut stream, &mut array) }, 0);
+        assert!(array.release.is_none());
+        assert_eq!(read.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_schema_metadata() -> Result<()> {
+        let metadata = [("source".to_string(), "kafka".to_string())]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>();
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![Field::new("a", DataType::Int32, true)],
+            metadata.clone(),
+        ));
+        let column = Arc::new(Int32Array::from(vec![Some(1), None])) as ArrayRef;
+        let batch = RecordBatch::try_new(schema.clone(), vec![column])?;
+        let read = Arc::new(AtomicUsize::new(0));
+        let reader = TestReader {
+            schema,
+            batches: vec![Ok(batch)].into_iter(),
+            read,
+        };
+        let mut stream = FFI_ArrowArrayStream::new(Box::new(reader));
+
+        // (simulate consumer) import it
+        let reader = unsafe { ArrowArrayStreamReader::from_raw(&mut stream) }?;
+        assert_eq!(reader.schema().metadata(), &metadata);
+
+        let batches = reader.collect::<Result<Vec<_>>>()?;
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 2);
+        Ok(())
+    }
+}
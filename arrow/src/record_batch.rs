@@ -18,11 +18,13 @@
 //! A two-dimensional batch of column-oriented data with a defined
 //! [schema](crate::datatypes::Schema).
 
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 use crate::array::*;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
+use crate::ffi;
 
 /// A two-dimensional batch of column-oriented data with a defined
 /// [schema](crate::datatypes::Schema).
@@ -328,6 +330,68 @@ impl RecordBatch {
         let schema = Arc::new(Schema::new(fields));
         RecordBatch::try_new(schema, columns)
     }
+
+    /// returns two pointers that represent this batch in the C Data Interface (FFI), as a
+    /// struct array whose children are the columns of this batch. The children's schemas
+    /// carry the names and nullability of the fields of this batch, and the struct's
+    /// schema carries the metadata of the batch's schema.
+    pub fn to_raw(
+        &self,
+    ) -> Result<(*const ffi::FFI_ArrowArray, *const ffi::FFI_ArrowSchema)> {
+        let schema = Arc::new(ffi::FFI_ArrowSchema::try_from(self.schema().as_ref())?);
+        let array = StructArray::from(self.clone());
+        let array = unsafe {
+            ffi::ArrowArray::try_new_with_schema(array.data().clone(), schema)
+        }?;
+        Ok(ffi::ArrowArray::into_raw(array))
+    }
+
+    /// Creates a new batch from two FFI pointers of a struct array, e.g. exported via
+    /// [RecordBatch::to_raw]: each child of the struct array is a column of the batch.
+    /// # Safety
+    /// Assumes that these pointers represent valid C Data Interfaces, both in memory
+    /// representation and lifetime via the `release` mechanism.
+    /// # Error
+    /// Errors if the array is not a struct array, or if it has nulls, which a batch can't
+    /// represent.
+    pub unsafe fn from_raw(
+        array: *const ffi::FFI_ArrowArray,
+        schema: *const ffi::FFI_ArrowSchema,
+    ) -> Result<Self> {
+        let array = ffi::ArrowArray::try_from_raw(array, schema)?;
+        let field = array.field()?;
+        let fields = match field.data_type() {
+            DataType::Struct(fields) => fields.clone(),
+            data_type => {
+                return Err(ArrowError::CDataInterface(format!(
+                    "A record batch must be imported from a struct array, but the array is of type {:?}",
+                    data_type
+                )))
+            }
+        };
+        let data = ArrayData::try_from(array)?;
+        if data.null_count() != 0 {
+            return Err(ArrowError::CDataInterface(format!(
+                "A record batch can't be imported from a struct array with nulls, but it has {} nulls",
+                data.null_count()
+            )));
+        }
+        let columns = data
+            .child_data()
+            .iter()
+            .map(|child| make_array(child.slice(data.offset(), data.len())))
+            .collect();
+        let metadata = field
+            .metadata()
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        RecordBatch::try_new(
+            Arc::new(Schema::new_with_metadata(fields, metadata)),
+            columns,
+        )
+    }
 }
 
 /// Options that control the behaviour used when creating a [`RecordBatch`].
@@ -399,6 +463,8 @@ pub trait RecordBatchReader: Iterator<Item = Result<RecordBatch>> {
 mod tests {
     use super::*;
 
+    use std::collections::HashMap;
+
     use crate::buffer::Buffer;
 
     #[test]
@@ -562,4 +628,40 @@ mod tests {
         assert_eq!(batch.column(0).as_ref(), boolean.as_ref());
         assert_eq!(batch.column(1).as_ref(), int.as_ref());
     }
+
+    #[test]
+    fn test_ffi_round_trip() -> Result<()> {
+        let metadata = [("key".to_string(), "value".to_string())]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>();
+        let schema = Schema::new_with_metadata(
+            vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, false),
+                Field::new("c", DataType::Boolean, true),
+            ],
+            metadata.clone(),
+        );
+        let a = Int32Array::from(vec![Some(1), None, Some(3)]);
+        let b = StringArray::from(vec!["a", "b", "c"]);
+        let c = BooleanArray::from(vec![Some(true), Some(false), None]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(a), Arc::new(b), Arc::new(c)],
+        )?;
+
+        // export it
+        let (array, schema) = batch.to_raw()?;
+
+        // (simulate consumer) import it
+        let imported = unsafe { RecordBatch::from_raw(array, schema) }?;
+        assert_eq!(imported.schema(), batch.schema());
+        assert_eq!(imported.schema().metadata(), &metadata);
+        assert_eq!(imported.num_rows(), 3);
+        for i in 0..3 {
+            assert_eq!(imported.column(i).data(), batch.column(i).data());
+        }
+        Ok(())
+    }
 }
@@ -82,8 +82,9 @@ macro_rules! make_string_interval_day_time {
         } else {
             let value: u64 = array.value($row) as u64;
 
-            let days_parts: i32 = ((value & 0xFFFFFFFF00000000) >> 32) as i32;
-            let milliseconds_part: i32 = (value & 0xFFFFFFFF) as i32;
+            // as in Arrow C++, the days precede the milliseconds in (little-endian) memory
+            let days_parts: i32 = (value & 0xFFFFFFFF) as i32;
+            let milliseconds_part: i32 = ((value & 0xFFFFFFFF00000000) >> 32) as i32;
 
             let secs = milliseconds_part / 1000;
             let mins = secs / 60;